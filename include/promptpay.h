@@ -0,0 +1,169 @@
+#ifndef PROMPTPAY_H
+#define PROMPTPAY_H
+
+/* Warning: this file is generated by cbindgen from src/ffi.rs. Do not edit it by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * `input_type` of `promptpay_generate` for a mobile number.
+ */
+#define PROMPTPAY_INPUT_PHONE_NUMBER 0
+
+/**
+ * `input_type` of `promptpay_generate` for a 13-digit national ID or tax ID.
+ */
+#define PROMPTPAY_INPUT_NATIONAL_ID 1
+
+/**
+ * `input_type` of `promptpay_generate` for a 15-digit e-wallet ID.
+ */
+#define PROMPTPAY_INPUT_EWALLET_ID 2
+
+/**
+ * Result of every fallible C function. On failure, `promptpay_last_error` describes the
+ * error in detail.
+ */
+typedef enum PromptpayStatus {
+  /**
+   * The call succeeded.
+   */
+  PROMPTPAY_STATUS_OK = 0,
+  /**
+   * A required pointer argument was null.
+   */
+  PROMPTPAY_STATUS_NULL_POINTER = 1,
+  /**
+   * A string argument was not valid UTF-8.
+   */
+  PROMPTPAY_STATUS_INVALID_UTF8 = 2,
+  /**
+   * `input_type` is not one of the `PROMPTPAY_INPUT_*` constants.
+   */
+  PROMPTPAY_STATUS_UNKNOWN_INPUT_TYPE = 3,
+  /**
+   * The proxy, bank account or bill payment fields are invalid.
+   */
+  PROMPTPAY_STATUS_INVALID_INPUT = 4,
+  /**
+   * The amount is malformed, negative, too large or too precise.
+   */
+  PROMPTPAY_STATUS_INVALID_AMOUNT = 5,
+  /**
+   * The payload string is not a well-formed PromptPay payload.
+   */
+  PROMPTPAY_STATUS_INVALID_PAYLOAD = 6,
+  /**
+   * The CRC of the payload string does not match its contents.
+   */
+  PROMPTPAY_STATUS_CRC_MISMATCH = 7,
+  /**
+   * An unexpected internal error; please report it.
+   */
+  PROMPTPAY_STATUS_INTERNAL_ERROR = 8,
+} PromptpayStatus;
+
+
+
+
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Generate a PromptPay credit transfer payload to a mobile number, national ID or e-wallet.
+ *
+ * `input_type` is one of the `PROMPTPAY_INPUT_*` constants and `value` the number or ID in
+ * any common format, e.g. "081-234-5678". `amount` is a decimal string in Baht such as
+ * "123.45", or null for a static QR code where the payer enters the amount.
+ *
+ * On success `*out_payload` receives the payload string, to be freed with
+ * `promptpay_free_string`.
+ *
+ * # Safety
+ * `value` and `amount` must be null or NUL-terminated strings, and `out_payload` must be
+ * null or valid for writes.
+ */
+enum PromptpayStatus promptpay_generate(uint32_t input_type,
+                                        const char *value,
+                                        const char *amount,
+                                        char **out_payload);
+
+/**
+ * Generate a PromptPay credit transfer payload to a bank account.
+ *
+ * `bank_code` is the 3-digit bank code, e.g. "004", and `amount` is as for
+ * `promptpay_generate`.
+ *
+ * # Safety
+ * The string arguments must be null or NUL-terminated strings, and `out_payload` must be
+ * null or valid for writes.
+ */
+enum PromptpayStatus promptpay_generate_bank_account(const char *bank_code,
+                                                     const char *account_number,
+                                                     const char *amount,
+                                                     char **out_payload);
+
+/**
+ * Generate a Thai QR bill payment payload.
+ *
+ * `reference2` may be null. `amount` is as for `promptpay_generate`.
+ *
+ * # Safety
+ * The string arguments must be null or NUL-terminated strings, and `out_payload` must be
+ * null or valid for writes.
+ */
+enum PromptpayStatus promptpay_generate_bill_payment(const char *biller_id,
+                                                     const char *reference1,
+                                                     const char *reference2,
+                                                     const char *amount,
+                                                     char **out_payload);
+
+/**
+ * Parse a payload string, verifying its CRC.
+ *
+ * On success `*out_json` receives the payload fields as a JSON object, in the same form as
+ * the `serde` feature, to be freed with `promptpay_free_string`.
+ *
+ * # Safety
+ * `payload` must be null or a NUL-terminated string, and `out_json` must be null or valid
+ * for writes.
+ */
+enum PromptpayStatus promptpay_parse(const char *payload, char **out_json);
+
+/**
+ * Calculate the CRC-16 of `data` as 4 uppercase hexadecimal digits, as
+ * `Utils::calculate_precise_crc` does for the CRC data object that ends every payload.
+ *
+ * On success `*out_crc` receives the CRC, to be freed with `promptpay_free_string`.
+ *
+ * # Safety
+ * `data` must be null or a NUL-terminated string, and `out_crc` must be null or valid for
+ * writes.
+ */
+enum PromptpayStatus promptpay_calculate_crc(const char *data, char **out_crc);
+
+/**
+ * Free a string returned by this library. Null is ignored.
+ *
+ * # Safety
+ * `value` must be null or a string returned by this library that has not been freed yet.
+ */
+void promptpay_free_string(char *value);
+
+/**
+ * The message of the last failed call on the calling thread, or null if the last call
+ * succeeded. The string belongs to the library and stays valid until the next call on the
+ * same thread.
+ */
+const char *promptpay_last_error(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* PROMPTPAY_H */
@@ -0,0 +1,226 @@
+use crate::emv::{self, DataObject};
+use crate::error::{Field, PromptPayError};
+
+/// Sub-tags of the Additional Data Field Template.
+const BILL_NUMBER: &str = "01";
+const MOBILE_NUMBER: &str = "02";
+const STORE_LABEL: &str = "03";
+const LOYALTY_NUMBER: &str = "04";
+const REFERENCE_LABEL: &str = "05";
+const CUSTOMER_LABEL: &str = "06";
+const TERMINAL_LABEL: &str = "07";
+const PURPOSE_OF_TRANSACTION: &str = "08";
+
+/// The longest value allowed for any of the sub-tags above.
+const MAX_FIELD_LENGTH: usize = 25;
+
+/// Additional Data Field Template (tag 62), used to tell branches, tills and bills apart
+/// during reconciliation.
+///
+/// Every field is optional. Set them with the chained methods of the same name:
+///
+/// ```
+/// use prompt_pay::additional_data::AdditionalData;
+///
+/// let data = AdditionalData::new()
+///     .bill_number("INV-2024-001")
+///     .store_label("Branch 12")
+///     .terminal_label("POS3");
+/// assert_eq!(data.terminal_label.as_deref(), Some("POS3"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdditionalData {
+    /// Invoice or bill number (sub-tag 01).
+    pub bill_number: Option<String>,
+    /// Mobile number, e.g. for top-ups (sub-tag 02).
+    pub mobile_number: Option<String>,
+    /// Store or branch label (sub-tag 03).
+    pub store_label: Option<String>,
+    /// Loyalty card number (sub-tag 04).
+    pub loyalty_number: Option<String>,
+    /// Reference label, e.g. an order number (sub-tag 05).
+    pub reference_label: Option<String>,
+    /// Customer label, e.g. a customer number (sub-tag 06).
+    pub customer_label: Option<String>,
+    /// Terminal or till label (sub-tag 07).
+    pub terminal_label: Option<String>,
+    /// Purpose of the transaction (sub-tag 08).
+    pub purpose_of_transaction: Option<String>,
+}
+
+impl AdditionalData {
+    /// Create an empty Additional Data Field Template.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the bill number (sub-tag 01).
+    pub fn bill_number(mut self, value: impl Into<String>) -> Self {
+        self.bill_number = Some(value.into());
+        self
+    }
+
+    /// Set the mobile number (sub-tag 02).
+    pub fn mobile_number(mut self, value: impl Into<String>) -> Self {
+        self.mobile_number = Some(value.into());
+        self
+    }
+
+    /// Set the store label (sub-tag 03).
+    pub fn store_label(mut self, value: impl Into<String>) -> Self {
+        self.store_label = Some(value.into());
+        self
+    }
+
+    /// Set the loyalty number (sub-tag 04).
+    pub fn loyalty_number(mut self, value: impl Into<String>) -> Self {
+        self.loyalty_number = Some(value.into());
+        self
+    }
+
+    /// Set the reference label (sub-tag 05).
+    pub fn reference_label(mut self, value: impl Into<String>) -> Self {
+        self.reference_label = Some(value.into());
+        self
+    }
+
+    /// Set the customer label (sub-tag 06).
+    pub fn customer_label(mut self, value: impl Into<String>) -> Self {
+        self.customer_label = Some(value.into());
+        self
+    }
+
+    /// Set the terminal label (sub-tag 07).
+    pub fn terminal_label(mut self, value: impl Into<String>) -> Self {
+        self.terminal_label = Some(value.into());
+        self
+    }
+
+    /// Set the purpose of transaction (sub-tag 08).
+    pub fn purpose_of_transaction(mut self, value: impl Into<String>) -> Self {
+        self.purpose_of_transaction = Some(value.into());
+        self
+    }
+
+    /// Whether no field is set.
+    pub fn is_empty(&self) -> bool {
+        self.fields().iter().all(|(_, _, value)| value.is_none())
+    }
+
+    /// The fields with their sub-tags, in sub-tag order.
+    fn fields(&self) -> [(&'static str, Field, &Option<String>); 8] {
+        [
+            (BILL_NUMBER, Field::BillNumber, &self.bill_number),
+            (MOBILE_NUMBER, Field::MobileNumber, &self.mobile_number),
+            (STORE_LABEL, Field::StoreLabel, &self.store_label),
+            (LOYALTY_NUMBER, Field::LoyaltyNumber, &self.loyalty_number),
+            (
+                REFERENCE_LABEL,
+                Field::ReferenceLabel,
+                &self.reference_label,
+            ),
+            (CUSTOMER_LABEL, Field::CustomerLabel, &self.customer_label),
+            (TERMINAL_LABEL, Field::TerminalLabel, &self.terminal_label),
+            (
+                PURPOSE_OF_TRANSACTION,
+                Field::PurposeOfTransaction,
+                &self.purpose_of_transaction,
+            ),
+        ]
+    }
+
+    /// Build the tag 62 template, checking each field's length and character set.
+    ///
+    /// # Returns
+    /// The template, `None` if no field is set, or an error naming the invalid field.
+    pub(crate) fn to_data_object(&self) -> Result<Option<DataObject>, PromptPayError> {
+        let mut children = Vec::new();
+
+        for (tag, field, value) in self.fields() {
+            let value = match value {
+                Some(value) => value,
+                None => continue,
+            };
+
+            emv::check_text(field, value, MAX_FIELD_LENGTH)?;
+            children.push(DataObject::primitive(tag, value.as_str()));
+        }
+
+        if children.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(DataObject::template(
+            emv::ADDITIONAL_DATA_FIELD_TEMPLATE,
+            children,
+        )))
+    }
+
+    /// Read the fields of a decoded tag 62 template, ignoring unknown sub-tags.
+    pub(crate) fn from_data_objects(children: &[DataObject]) -> Self {
+        let value = |tag| {
+            emv::find(children, tag)
+                .and_then(DataObject::as_str)
+                .map(str::to_string)
+        };
+
+        AdditionalData {
+            bill_number: value(BILL_NUMBER),
+            mobile_number: value(MOBILE_NUMBER),
+            store_label: value(STORE_LABEL),
+            loyalty_number: value(LOYALTY_NUMBER),
+            reference_label: value(REFERENCE_LABEL),
+            customer_label: value(CUSTOMER_LABEL),
+            terminal_label: value(TERMINAL_LABEL),
+            purpose_of_transaction: value(PURPOSE_OF_TRANSACTION),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AdditionalData;
+    use crate::error::{Field, PromptPayError};
+
+    #[test]
+    fn test_additional_data_encoding() {
+        let data = AdditionalData::new()
+            .bill_number("INV001")
+            .terminal_label("POS3");
+        let object = data.to_data_object().unwrap().unwrap();
+        assert_eq!(object.encode().unwrap(), "62180106INV0010704POS3");
+    }
+
+    #[test]
+    fn test_additional_data_empty() {
+        assert!(AdditionalData::new().is_empty());
+        assert_eq!(AdditionalData::new().to_data_object().unwrap(), None);
+    }
+
+    #[test]
+    fn test_additional_data_too_long() {
+        let data = AdditionalData::new().store_label("X".repeat(26));
+        let result = data.to_data_object();
+        assert_eq!(
+            result.err().unwrap(),
+            PromptPayError::LengthOutOfRange {
+                field: Field::StoreLabel,
+                min: 1,
+                max: 25,
+                actual: 26
+            }
+        );
+    }
+
+    #[test]
+    fn test_additional_data_invalid_characters() {
+        let data = AdditionalData::new().customer_label("สมชาย");
+        assert_eq!(
+            data.to_data_object().err().unwrap(),
+            PromptPayError::InvalidCharacter {
+                field: Field::CustomerLabel,
+                character: 'ส'
+            }
+        );
+    }
+}
@@ -0,0 +1,506 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::currency::Currency;
+use crate::error::{Field, PromptPayError};
+
+/// The longest value the EMVCo transaction amount field (tag 54) may hold.
+pub const MAX_ENCODED_LENGTH: usize = 13;
+
+/// How the transaction amount is padded when it is written into a payload.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+pub enum AmountPadding {
+    /// Write the amount as is, e.g. "123.45".
+    #[default]
+    None,
+    /// Left-pad the amount with zeros to the given width, e.g. "000123.45" for a width of 9.
+    ZeroPadded(usize),
+}
+
+/// An exact amount of money, stored as a whole number of minor units of the payload
+/// currency: satang for Thai Baht (1 Baht = 100 satang), cents for US Dollars, whole yen
+/// for Japanese Yen and so on.
+///
+/// Amounts are never negative and never exceed [`Amount::MAX`] minor units, which fits the
+/// 13-character EMVCo amount field whatever the number of decimals of the currency.
+///
+/// Parsing with [`str::parse`] and formatting with `Display` always use the two decimals of
+/// Baht, whatever the payload currency, so they give the wrong value for currencies such as
+/// Japanese Yen (no decimals) or Kuwaiti Dinar (three decimals). Use [`Amount::parse_in`] and
+/// [`Amount::format_in`] for anything other than Baht.
+///
+/// ```
+/// use prompt_pay::amount::Amount;
+///
+/// let amount: Amount = "1,234.50".parse().unwrap();
+/// assert_eq!(amount.minor_units(), 123_450);
+/// assert_eq!(amount.to_string(), "1234.50");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Amount(u64);
+
+impl Amount {
+    /// The largest amount a payload can carry: 9,999,999,999.99 Baht.
+    pub const MAX: Amount = Amount(999_999_999_999);
+
+    /// Create an amount from a whole number of minor units of the payload currency.
+    ///
+    /// # Returns
+    /// The amount, or an error if it is larger than [`Amount::MAX`].
+    pub fn from_minor_units(units: u64) -> Result<Self, PromptPayError> {
+        if units > Self::MAX.0 {
+            return Err(PromptPayError::AmountTooLarge);
+        }
+        Ok(Amount(units))
+    }
+
+    /// The amount in minor units of the payload currency.
+    pub fn minor_units(&self) -> u64 {
+        self.0
+    }
+
+    /// Whether the amount is zero.
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Parse an amount such as "1,234.5" in the given currency, which may have at most as
+    /// many decimals as the currency has minor-unit digits.
+    ///
+    /// ```
+    /// use prompt_pay::amount::Amount;
+    /// use prompt_pay::currency::Currency;
+    ///
+    /// let dinar = Currency::from_alpha("KWD").unwrap();
+    /// assert_eq!(Amount::parse_in("1.25", dinar).unwrap().minor_units(), 1_250);
+    ///
+    /// let yen = Currency::from_alpha("JPY").unwrap();
+    /// assert!(Amount::parse_in("1500.50", yen).is_err());
+    /// ```
+    pub fn parse_in(input: &str, currency: Currency) -> Result<Self, PromptPayError> {
+        let decimals = usize::from(currency.minor_units());
+        let invalid = || PromptPayError::InvalidValue {
+            field: Field::Amount,
+            value: input.to_string(),
+        };
+        let input = input.trim();
+
+        let (whole, fraction) = match input.split_once('.') {
+            Some((whole, fraction)) => (whole, fraction),
+            None => (input, ""),
+        };
+
+        // Thousands separators are optional, but must be placed correctly when present
+        let groups: Vec<&str> = whole.split(',').collect();
+        let grouped_correctly = groups.len() == 1
+            || (!groups[0].is_empty()
+                && groups[0].len() <= 3
+                && groups[1..].iter().all(|group| group.len() == 3));
+        let whole: String = groups.concat();
+
+        if whole.is_empty()
+            || !grouped_correctly
+            || fraction.len() > decimals
+            || !whole
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let major: u64 = whole.parse().map_err(|_| PromptPayError::AmountTooLarge)?;
+        // Right-pad the fraction so "1.5" in a 2-decimal currency is 150 minor units
+        let minor: u64 = format!("{:0<width$}", fraction, width = decimals)
+            .parse()
+            .unwrap_or(0);
+
+        10u64
+            .checked_pow(decimals as u32)
+            .and_then(|scale| major.checked_mul(scale))
+            .and_then(|units| units.checked_add(minor))
+            .ok_or(PromptPayError::AmountTooLarge)
+            .and_then(Self::from_minor_units)
+    }
+
+    /// Format the amount with the decimals of the given currency: 1,250 minor units are
+    /// "12.50" in Baht, "1250" in Yen and "1.250" in Kuwaiti Dinar.
+    pub fn format_in(&self, currency: Currency) -> String {
+        let decimals = usize::from(currency.minor_units());
+        if decimals == 0 {
+            return self.0.to_string();
+        }
+
+        let digits = format!("{:0>width$}", self.0, width = decimals + 1);
+        let (major, minor) = digits.split_at(digits.len() - decimals);
+        format!("{}.{}", major, minor)
+    }
+
+    /// Format the amount for the transaction amount field.
+    ///
+    /// # Parameters
+    /// - `currency`: The payload currency, which sets the number of decimals.
+    /// - `padding`: Whether to left-pad the amount with zeros.
+    ///
+    /// # Returns
+    /// The formatted amount, or an error if it would not fit in the
+    /// [`MAX_ENCODED_LENGTH`] characters of the field.
+    pub fn encode(
+        &self,
+        currency: Currency,
+        padding: AmountPadding,
+    ) -> Result<String, PromptPayError> {
+        let amount = self.format_in(currency);
+        let formatted = match padding {
+            AmountPadding::None => amount,
+            AmountPadding::ZeroPadded(width) if width > MAX_ENCODED_LENGTH => {
+                return Err(PromptPayError::LengthOutOfRange {
+                    field: Field::Amount,
+                    min: 1,
+                    max: MAX_ENCODED_LENGTH,
+                    actual: width,
+                });
+            }
+            AmountPadding::ZeroPadded(width) => format!("{:0>width$}", amount, width = width),
+        };
+
+        if formatted.len() > MAX_ENCODED_LENGTH {
+            return Err(PromptPayError::LengthOutOfRange {
+                field: Field::Amount,
+                min: 1,
+                max: MAX_ENCODED_LENGTH,
+                actual: formatted.len(),
+            });
+        }
+        Ok(formatted)
+    }
+
+    /// Parse the value of a transaction amount field, which holds digits and an optional
+    /// decimal point only.
+    ///
+    /// # Parameters
+    /// - `value`: The value of the field.
+    /// - `currency`: The payload currency, which sets the number of decimals allowed.
+    ///
+    /// # Returns
+    /// The amount with the padding it was written with, or an error if the value is malformed.
+    pub fn decode(
+        value: &str,
+        currency: Currency,
+    ) -> Result<(Self, AmountPadding), PromptPayError> {
+        if value.is_empty()
+            || value.len() > MAX_ENCODED_LENGTH
+            || !value.chars().all(|c| c.is_ascii_digit() || c == '.')
+        {
+            return Err(PromptPayError::InvalidValue {
+                field: Field::Amount,
+                value: value.to_string(),
+            });
+        }
+
+        let amount = Amount::parse_in(value, currency)?;
+        let padding = if value.starts_with('0') && value.len() > amount.format_in(currency).len() {
+            AmountPadding::ZeroPadded(value.len())
+        } else {
+            AmountPadding::None
+        };
+        Ok((amount, padding))
+    }
+}
+
+impl fmt::Display for Amount {
+    /// Format the amount in Baht with exactly two decimals, e.g. "1234.50".
+    ///
+    /// This ignores the payload currency; use [`Amount::format_in`] for other currencies.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.format_in(Currency::THB))
+    }
+}
+
+impl FromStr for Amount {
+    type Err = PromptPayError;
+
+    /// Parse an amount in Baht such as "1234.5", "1,234.50" or "500".
+    ///
+    /// Thousands separators must group the whole Baht in threes, and at most two decimals
+    /// are allowed since a satang cannot be split.
+    ///
+    /// This always reads Baht; use [`Amount::parse_in`] for other currencies.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Amount::parse_in(input, Currency::THB)
+    }
+}
+
+impl TryFrom<f64> for Amount {
+    type Error = PromptPayError;
+
+    /// Convert an amount in Baht, rounding to the nearest satang.
+    ///
+    /// NaN, infinite and negative values are rejected.
+    fn try_from(baht: f64) -> Result<Self, Self::Error> {
+        if !baht.is_finite() {
+            return Err(PromptPayError::NonFiniteAmount);
+        }
+        if baht < 0.0 {
+            return Err(PromptPayError::NegativeAmount);
+        }
+
+        let satang = (baht * 100.0).round();
+        if satang > Self::MAX.0 as f64 {
+            return Err(PromptPayError::AmountTooLarge);
+        }
+        Ok(Amount(satang as u64))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Amount {
+    /// Serialize the amount as a string in Baht, e.g. "1234.50", so it never passes through
+    /// floating point.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Amount {
+    /// Deserialize an amount in Baht from a string such as "1,234.50" or from a whole number
+    /// of Baht. Numbers with a fraction are rejected, since they have already been rounded to
+    /// floating point.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AmountVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for AmountVisitor {
+            type Value = Amount;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an amount in Baht as a string or a whole number")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Amount, E> {
+                value.parse().map_err(E::custom)
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, baht: u64) -> Result<Amount, E> {
+                baht.checked_mul(100)
+                    .ok_or(PromptPayError::AmountTooLarge)
+                    .and_then(Amount::from_minor_units)
+                    .map_err(E::custom)
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, baht: i64) -> Result<Amount, E> {
+                if baht < 0 {
+                    return Err(E::custom(PromptPayError::NegativeAmount));
+                }
+                self.visit_u64(baht as u64)
+            }
+        }
+
+        deserializer.deserialize_any(AmountVisitor)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl TryFrom<rust_decimal::Decimal> for Amount {
+    type Error = PromptPayError;
+
+    /// Convert an amount in Baht, which must be a whole number of satang.
+    fn try_from(baht: rust_decimal::Decimal) -> Result<Self, Self::Error> {
+        if baht.is_sign_negative() && !baht.is_zero() {
+            return Err(PromptPayError::NegativeAmount);
+        }
+
+        let satang = baht
+            .checked_mul(rust_decimal::Decimal::ONE_HUNDRED)
+            .ok_or(PromptPayError::AmountTooLarge)?;
+        if !satang.fract().is_zero() {
+            return Err(PromptPayError::AmountTooPrecise);
+        }
+
+        let satang = u64::try_from(satang).map_err(|_| PromptPayError::AmountTooLarge)?;
+        Self::from_minor_units(satang)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Amount, AmountPadding};
+    use crate::currency::Currency;
+    use crate::error::PromptPayError;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!("1,234.50".parse::<Amount>().unwrap().minor_units(), 123_450);
+        assert_eq!("1234.5".parse::<Amount>().unwrap().minor_units(), 123_450);
+        assert_eq!("500".parse::<Amount>().unwrap().minor_units(), 50_000);
+        assert_eq!("0.07".parse::<Amount>().unwrap().minor_units(), 7);
+        assert_eq!(
+            "1,000,000".parse::<Amount>().unwrap().minor_units(),
+            100_000_000
+        );
+    }
+
+    #[test]
+    fn test_parse_amount_invalid() {
+        for input in [
+            "",
+            "-5",
+            "1.234",
+            "12,34.00",
+            ",123",
+            "1e5",
+            "abc",
+            "10000000000",
+        ] {
+            assert!(
+                input.parse::<Amount>().is_err(),
+                "{} should not parse",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_amount_display() {
+        assert_eq!(Amount::from_minor_units(5).unwrap().to_string(), "0.05");
+        assert_eq!(
+            Amount::from_minor_units(10_000_000).unwrap().to_string(),
+            "100000.00"
+        );
+        assert_eq!(Amount::MAX.to_string(), "9999999999.99");
+    }
+
+    #[test]
+    fn test_amount_encode() {
+        let amount = Amount::from_minor_units(12_345).unwrap();
+        assert_eq!(
+            amount.encode(Currency::THB, AmountPadding::None).unwrap(),
+            "123.45"
+        );
+        assert_eq!(
+            amount
+                .encode(Currency::THB, AmountPadding::ZeroPadded(9))
+                .unwrap(),
+            "000123.45"
+        );
+        assert!(amount
+            .encode(Currency::THB, AmountPadding::ZeroPadded(14))
+            .is_err());
+
+        let large = Amount::from_minor_units(25_000_000).unwrap();
+        assert_eq!(
+            large
+                .encode(Currency::THB, AmountPadding::ZeroPadded(9))
+                .unwrap(),
+            "250000.00"
+        );
+        assert_eq!(
+            Amount::MAX
+                .encode(Currency::THB, AmountPadding::None)
+                .unwrap(),
+            "9999999999.99"
+        );
+    }
+
+    #[test]
+    fn test_amount_decode() {
+        let amount = Amount::from_minor_units(12_345).unwrap();
+        assert_eq!(
+            Amount::decode("000123.45", Currency::THB).unwrap(),
+            (amount, AmountPadding::ZeroPadded(9))
+        );
+        assert_eq!(
+            Amount::decode("123.45", Currency::THB).unwrap(),
+            (amount, AmountPadding::None)
+        );
+        assert!(Amount::decode("1,234.50", Currency::THB).is_err());
+        assert!(Amount::decode("00000000123.45", Currency::THB).is_err());
+    }
+
+    #[test]
+    fn test_amount_in_other_currencies() {
+        let yen = Currency::from_alpha("JPY").unwrap();
+        let dinar = Currency::from_alpha("KWD").unwrap();
+
+        let amount = Amount::parse_in("1,500", yen).unwrap();
+        assert_eq!(amount.minor_units(), 1_500);
+        assert_eq!(amount.encode(yen, AmountPadding::None).unwrap(), "1500");
+        assert!(Amount::parse_in("1500.5", yen).is_err());
+
+        let amount = Amount::parse_in("1.25", dinar).unwrap();
+        assert_eq!(amount.minor_units(), 1_250);
+        assert_eq!(amount.format_in(dinar), "1.250");
+        assert_eq!(
+            Amount::from_minor_units(5).unwrap().format_in(dinar),
+            "0.005"
+        );
+        assert_eq!(
+            Amount::decode("0001.250", dinar).unwrap(),
+            (amount, AmountPadding::ZeroPadded(8))
+        );
+    }
+
+    #[test]
+    fn test_amount_from_f64() {
+        assert_eq!(Amount::try_from(123.45).unwrap().minor_units(), 12_345);
+        assert_eq!(Amount::try_from(0.1 + 0.2).unwrap().minor_units(), 30);
+        assert_eq!(
+            Amount::try_from(f64::NAN),
+            Err(PromptPayError::NonFiniteAmount)
+        );
+        assert_eq!(
+            Amount::try_from(f64::INFINITY),
+            Err(PromptPayError::NonFiniteAmount)
+        );
+        assert_eq!(Amount::try_from(-1.0), Err(PromptPayError::NegativeAmount));
+        assert_eq!(Amount::try_from(1e13), Err(PromptPayError::AmountTooLarge));
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn test_amount_from_decimal() {
+        use rust_decimal::Decimal;
+        use std::str::FromStr;
+
+        let decimal = Decimal::from_str("1234.50").unwrap();
+        assert_eq!(Amount::try_from(decimal).unwrap().minor_units(), 123_450);
+        assert_eq!(
+            Amount::try_from(Decimal::from_str("-1").unwrap()),
+            Err(PromptPayError::NegativeAmount)
+        );
+        assert_eq!(
+            Amount::try_from(Decimal::from_str("1.005").unwrap()),
+            Err(PromptPayError::AmountTooPrecise)
+        );
+        assert_eq!(
+            Amount::try_from(Decimal::MAX),
+            Err(PromptPayError::AmountTooLarge)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_amount_serde() {
+        let amount = Amount::from_minor_units(123_450).unwrap();
+        assert_eq!(serde_json::to_string(&amount).unwrap(), r#""1234.50""#);
+        assert_eq!(
+            serde_json::from_str::<Amount>(r#""1,234.5""#).unwrap(),
+            amount
+        );
+        assert!(serde_json::from_str::<Amount>("1234.5").is_err());
+        assert!(serde_json::from_str::<Amount>("1.005").is_err());
+        assert_eq!(
+            serde_json::from_str::<Amount>("500").unwrap(),
+            Amount::from_minor_units(50_000).unwrap()
+        );
+        assert!(serde_json::from_str::<Amount>("-1").is_err());
+        assert!(serde_json::from_str::<Amount>(r#""1.005""#).is_err());
+    }
+}
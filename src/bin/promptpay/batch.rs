@@ -0,0 +1,365 @@
+//! Batch generation: one payload, and optionally one QR image, per row of a CSV or JSON
+//! Lines file.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+use clap::{Args, ValueEnum};
+use prompt_pay::render::png::PngOptions;
+use prompt_pay::render::svg::SvgOptions;
+
+use super::{extension, CliError, PayloadArgs, ProxyArgs};
+
+/// Columns naming the account to pay into; each row needs exactly one of them.
+const PROXY_COLUMNS: [&str; 5] = [
+    "phone",
+    "national_id",
+    "ewallet_id",
+    "account_number",
+    "biller_id",
+];
+
+/// Columns appended to the input columns in the output file.
+const RESULT_COLUMNS: [&str; 3] = ["payload", "image", "error"];
+
+#[derive(Debug, Args)]
+pub struct BatchArgs {
+    /// CSV or JSON Lines file with one payment per row. Columns are named after the
+    /// `generate` options, e.g. phone, national_id, amount, merchant_name, bill_number.
+    input: PathBuf,
+
+    /// Format of the input file, by default taken from its extension.
+    #[arg(long, value_enum)]
+    input_format: Option<InputFormat>,
+
+    /// Write the output CSV here instead of to standard output.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Write one QR image per row into this directory.
+    #[arg(long)]
+    images: Option<PathBuf>,
+
+    /// Format of the QR images.
+    #[arg(long, value_enum, default_value = "png")]
+    image_format: ImageFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum InputFormat {
+    Csv,
+    Jsonl,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ImageFormat {
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Svg => "svg",
+        }
+    }
+}
+
+/// The rows of an input file, each either its values by column or the reason it could not
+/// be read.
+struct Table {
+    columns: Vec<String>,
+    rows: Vec<Result<HashMap<String, String>, String>>,
+}
+
+/// Where and how to write the QR image of each row.
+struct Images<'a> {
+    directory: &'a Path,
+    format: ImageFormat,
+}
+
+pub fn run(args: BatchArgs) -> Result<(), CliError> {
+    let format = match args.input_format {
+        Some(format) => format,
+        None => match extension(&args.input).as_deref() {
+            Some("csv") => InputFormat::Csv,
+            Some("jsonl" | "ndjson") => InputFormat::Jsonl,
+            _ => return Err(CliError::UnknownInputFormat(args.input)),
+        },
+    };
+
+    let file = File::open(&args.input).map_err(|err| CliError::Io(args.input.clone(), err))?;
+    let table = match format {
+        InputFormat::Csv => read_csv(file)?,
+        InputFormat::Jsonl => {
+            read_jsonl(BufReader::new(file)).map_err(|err| CliError::Io(args.input.clone(), err))?
+        }
+    };
+
+    let images = match &args.images {
+        Some(directory) => {
+            fs::create_dir_all(directory).map_err(|err| CliError::Io(directory.clone(), err))?;
+            Some(Images {
+                directory,
+                format: args.image_format,
+            })
+        }
+        None => None,
+    };
+
+    let failed = match &args.output {
+        Some(path) => {
+            let file = File::create(path).map_err(|err| CliError::Io(path.clone(), err))?;
+            process(&table, images.as_ref(), file)?
+        }
+        None => process(&table, images.as_ref(), io::stdout().lock())?,
+    };
+
+    if failed > 0 {
+        return Err(CliError::RowsFailed {
+            failed,
+            total: table.rows.len(),
+        });
+    }
+    eprintln!("Generated {} payloads", table.rows.len());
+    Ok(())
+}
+
+fn read_csv<R: Read>(reader: R) -> Result<Table, CliError> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+    let columns: Vec<String> = reader.headers()?.iter().map(str::to_string).collect();
+
+    let rows = reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|err| err.to_string())?;
+            Ok(columns
+                .iter()
+                .cloned()
+                .zip(record.iter().map(str::to_string))
+                .collect())
+        })
+        .collect();
+    Ok(Table { columns, rows })
+}
+
+/// Read one JSON object per line, skipping blank lines. Columns are the keys in the order
+/// they first appear in the input.
+fn read_jsonl<R: BufRead>(reader: R) -> io::Result<Table> {
+    let mut columns: Vec<String> = Vec::new();
+    let mut rows = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let row = match serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&line) {
+            Ok(object) => Ok(object
+                .into_iter()
+                .filter_map(|(key, value)| {
+                    let value = match value {
+                        serde_json::Value::Null => return None,
+                        serde_json::Value::String(value) => value,
+                        value => value.to_string(),
+                    };
+                    if !columns.contains(&key) {
+                        columns.push(key.clone());
+                    }
+                    Some((key, value))
+                })
+                .collect()),
+            Err(err) => Err(format!("invalid JSON: {}", err)),
+        };
+        rows.push(row);
+    }
+    Ok(Table { columns, rows })
+}
+
+/// Generate the payload of every row and write the input columns followed by the payload,
+/// image path and error of each row as CSV. A failed row is reported and left without a
+/// payload; the rest of the batch still runs.
+///
+/// # Returns
+/// The number of rows that failed.
+fn process<W: Write>(table: &Table, images: Option<&Images>, writer: W) -> Result<usize, CliError> {
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record(
+        table
+            .columns
+            .iter()
+            .map(String::as_str)
+            .chain(RESULT_COLUMNS),
+    )?;
+
+    let mut failed = 0;
+    for (index, row) in table.rows.iter().enumerate() {
+        let number = index + 1;
+        let empty = HashMap::new();
+        let values = row.as_ref().unwrap_or(&empty);
+
+        let (payload, image, error) = match row
+            .as_ref()
+            .map_err(String::clone)
+            .and_then(|row| generate_row(row, number, images).map_err(|err| err.to_string()))
+        {
+            Ok((payload, image)) => (payload, image, String::new()),
+            Err(error) => {
+                eprintln!("row {}: {}", number, error);
+                failed += 1;
+                (String::new(), String::new(), error)
+            }
+        };
+
+        writer.write_record(
+            table
+                .columns
+                .iter()
+                .map(|column| values.get(column).map_or("", String::as_str))
+                .chain([payload.as_str(), image.as_str(), error.as_str()]),
+        )?;
+    }
+    writer.flush().map_err(csv::Error::from)?;
+    Ok(failed)
+}
+
+/// Generate the payload of one row, and its image if requested.
+///
+/// # Returns
+/// The payload string and the path of the image, or an empty path without images.
+fn generate_row(
+    row: &HashMap<String, String>,
+    number: usize,
+    images: Option<&Images>,
+) -> Result<(String, String), CliError> {
+    let payload = payload_args(row)?.build()?;
+    let encoded = payload.encode()?;
+
+    let image = match images {
+        Some(images) => {
+            let path =
+                images
+                    .directory
+                    .join(format!("{:04}.{}", number, images.format.extension()));
+            let contents = match images.format {
+                ImageFormat::Png => payload.to_png(&PngOptions::new())?,
+                ImageFormat::Svg => payload.to_svg(&SvgOptions::new())?.into_bytes(),
+            };
+            fs::write(&path, contents).map_err(|err| CliError::Io(path.clone(), err))?;
+            path.display().to_string()
+        }
+        None => String::new(),
+    };
+    Ok((encoded, image))
+}
+
+/// Read the payload fields of a row, treating blank values as absent.
+fn payload_args(row: &HashMap<String, String>) -> Result<PayloadArgs, CliError> {
+    let value = |column: &str| {
+        row.get(column)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+
+    let proxies = PROXY_COLUMNS
+        .iter()
+        .filter(|column| value(column).is_some())
+        .count();
+    if proxies != 1 {
+        return Err(CliError::InvalidRow(format!(
+            "expected exactly one of the {} columns",
+            PROXY_COLUMNS.join(", ")
+        )));
+    }
+
+    Ok(PayloadArgs {
+        proxy: ProxyArgs {
+            phone: value("phone"),
+            national_id: value("national_id"),
+            ewallet_id: value("ewallet_id"),
+            account_number: value("account_number"),
+            biller_id: value("biller_id"),
+            bank_code: value("bank_code"),
+            reference1: value("reference1"),
+            reference2: value("reference2"),
+        },
+        amount: value("amount"),
+        currency: value("currency").unwrap_or_else(|| "THB".to_string()),
+        merchant_name: value("merchant_name"),
+        merchant_city: value("merchant_city"),
+        mcc: value("mcc"),
+        postal_code: value("postal_code"),
+        bill_number: value("bill_number"),
+        reference_label: value("reference_label"),
+        terminal_label: value("terminal_label"),
+        tip: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{process, read_csv, read_jsonl};
+    use prompt_pay::promptpay_utils::{InputType, Utils};
+
+    fn output(table: &super::Table) -> (usize, Vec<Vec<String>>) {
+        let mut output = Vec::new();
+        let failed = process(table, None, &mut output).unwrap();
+        let rows = csv::Reader::from_reader(output.as_slice())
+            .records()
+            .map(|record| record.unwrap().iter().map(str::to_string).collect())
+            .collect();
+        (failed, rows)
+    }
+
+    #[test]
+    fn test_batch_csv() {
+        let input = "tenant,phone,national_id,amount\n\
+                     A101,081-234-5678,,1500\n\
+                     A102,,1234567890121,2500.50\n\
+                     A103,0812,,100\n\
+                     A104,,,100\n";
+        let (failed, rows) = output(&read_csv(input.as_bytes()).unwrap());
+        assert_eq!(failed, 2);
+        assert_eq!(rows.len(), 4);
+
+        let expected =
+            Utils::generate_payload(InputType::PhoneNumber("0812345678".to_string()), 1500.0);
+        assert_eq!(rows[0][0], "A101");
+        assert_eq!(rows[0][4], expected.unwrap());
+        assert_eq!(rows[0][6], "");
+
+        let expected =
+            Utils::generate_payload(InputType::NationalID("1234567890121".to_string()), 2500.5);
+        assert_eq!(rows[1][4], expected.unwrap());
+
+        // Invalid rows keep their input columns and carry the validation error
+        let error = Utils::sanitize_phone_number("0812".to_string()).unwrap_err();
+        assert_eq!(rows[2][..4], ["A103", "0812", "", "100"]);
+        assert_eq!(rows[2][4], "");
+        assert_eq!(rows[2][6], error.to_string());
+        assert!(rows[3][6].starts_with("expected exactly one of"));
+    }
+
+    #[test]
+    fn test_batch_jsonl() {
+        let input = "{\"phone\":\"0812345678\",\"amount\":1500}\n\
+                     \n\
+                     not json\n\
+                     {\"national_id\":\"1234567890120\",\"amount\":\"10\"}\n";
+        let table = read_jsonl(input.as_bytes()).unwrap();
+        assert_eq!(table.columns, ["phone", "amount", "national_id"]);
+
+        let (failed, rows) = output(&table);
+        assert_eq!(failed, 2);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0][..2], ["0812345678", "1500"]);
+        assert!(!rows[0][3].is_empty());
+        assert!(rows[1][5].starts_with("invalid JSON"));
+        let error = Utils::sanitize_national_id("1234567890120".to_string()).unwrap_err();
+        assert_eq!(rows[2][5], error.to_string());
+    }
+}
@@ -0,0 +1,487 @@
+//! `promptpay` command-line tool: generate, parse and verify PromptPay payloads.
+
+mod batch;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{ArgGroup, Args, Parser, Subcommand};
+use prompt_pay::promptpay_utils::{
+    AdditionalData, Amount, Currency, InputType, PromptPayBuilder, PromptPayError,
+    PromptPayPayload, Proxy, TipOrConvenienceFee, Utils,
+};
+use prompt_pay::render::png::{FrameOptions, PngOptions};
+use prompt_pay::render::svg::SvgOptions;
+use prompt_pay::render::terminal::TerminalOptions;
+
+/// Generate, parse and verify PromptPay QR payloads.
+#[derive(Debug, Parser)]
+#[command(name = "promptpay", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Generate a payload string, and optionally a QR image of it.
+    Generate(Box<GenerateArgs>),
+    /// Decode a payload string and print its fields.
+    Parse {
+        /// The payload string, as read from a QR code.
+        payload: String,
+    },
+    /// Check the CRC and structure of a payload string.
+    Verify {
+        /// The payload string, as read from a QR code.
+        payload: String,
+    },
+    /// Generate a payload, and optionally a QR image, for every row of a CSV or JSON Lines
+    /// file. Rows that fail validation are reported without stopping the batch.
+    Batch(batch::BatchArgs),
+}
+
+#[derive(Debug, Args)]
+struct GenerateArgs {
+    #[command(flatten)]
+    payload: PayloadArgs,
+
+    /// Write the QR code to this file, as SVG or PNG depending on the extension.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Draw a Thai QR Payment frame around a PNG QR code, with the payee name and amount.
+    #[arg(long, requires = "output")]
+    frame: bool,
+
+    /// Font for the frame text, tried before the bundled Thai and Latin font. Repeat for
+    /// fallback fonts. Implies --frame.
+    #[arg(long = "frame-font", requires = "output")]
+    frame_fonts: Vec<PathBuf>,
+
+    /// Print the QR code to the terminal after the payload string.
+    #[arg(long)]
+    show: bool,
+}
+
+/// The fields of the payload to generate.
+#[derive(Debug, Args)]
+struct PayloadArgs {
+    #[command(flatten)]
+    proxy: ProxyArgs,
+
+    /// Amount to pay, e.g. "250" or "1,234.50". Without it the QR code is static and the
+    /// payer enters the amount.
+    #[arg(long)]
+    amount: Option<String>,
+
+    /// Transaction currency, as an ISO 4217 alphabetic or numeric code.
+    #[arg(long, default_value = "THB")]
+    currency: String,
+
+    /// Payee name, up to 25 characters.
+    #[arg(long)]
+    merchant_name: Option<String>,
+
+    /// Payee city, up to 15 characters.
+    #[arg(long)]
+    merchant_city: Option<String>,
+
+    /// Merchant Category Code, e.g. "5812" for restaurants.
+    #[arg(long)]
+    mcc: Option<String>,
+
+    /// Payee postal code.
+    #[arg(long)]
+    postal_code: Option<String>,
+
+    /// Invoice or bill number.
+    #[arg(long)]
+    bill_number: Option<String>,
+
+    /// Reference label, e.g. an order number.
+    #[arg(long)]
+    reference_label: Option<String>,
+
+    /// Terminal or till label.
+    #[arg(long)]
+    terminal_label: Option<String>,
+
+    /// Prompt the payer to add a tip.
+    #[arg(long)]
+    tip: bool,
+}
+
+/// The account to pay into; exactly one of these is required.
+#[derive(Debug, Args)]
+#[group(skip)]
+#[command(group = ArgGroup::new("proxy").required(true))]
+struct ProxyArgs {
+    /// Mobile number registered with PromptPay.
+    #[arg(long, group = "proxy")]
+    phone: Option<String>,
+
+    /// 13-digit national ID or tax ID.
+    #[arg(long, group = "proxy")]
+    national_id: Option<String>,
+
+    /// 15-digit e-wallet ID.
+    #[arg(long, group = "proxy")]
+    ewallet_id: Option<String>,
+
+    /// Bank account number, together with --bank-code.
+    #[arg(long, group = "proxy", requires = "bank_code")]
+    account_number: Option<String>,
+
+    /// 15-digit biller ID for a bill payment, together with --reference1.
+    #[arg(long, group = "proxy", requires = "reference1")]
+    biller_id: Option<String>,
+
+    /// 3-digit bank code of --account-number.
+    #[arg(long, conflicts_with_all = ["phone", "national_id", "ewallet_id", "biller_id"])]
+    bank_code: Option<String>,
+
+    /// Bill payment reference 1.
+    #[arg(long, conflicts_with_all = ["phone", "national_id", "ewallet_id", "account_number"])]
+    reference1: Option<String>,
+
+    /// Bill payment reference 2.
+    #[arg(long, conflicts_with_all = ["phone", "national_id", "ewallet_id", "account_number"])]
+    reference2: Option<String>,
+}
+
+impl ProxyArgs {
+    /// The input type named by the arguments; clap guarantees exactly one is set.
+    fn into_input(self) -> InputType {
+        if let Some(phone) = self.phone {
+            InputType::PhoneNumber(phone)
+        } else if let Some(id) = self.national_id {
+            InputType::NationalID(id)
+        } else if let Some(id) = self.ewallet_id {
+            InputType::EWalletId(id)
+        } else if let Some(account_number) = self.account_number {
+            InputType::BankAccount {
+                bank_code: self.bank_code.unwrap_or_default(),
+                account_number,
+            }
+        } else {
+            InputType::BillPayment {
+                biller_id: self.biller_id.unwrap_or_default(),
+                reference1: self.reference1.unwrap_or_default(),
+                reference2: self.reference2,
+            }
+        }
+    }
+}
+
+/// A failed command, reported on stderr.
+#[derive(Debug)]
+enum CliError {
+    PromptPay(PromptPayError),
+    Io(PathBuf, std::io::Error),
+    UnknownImageFormat(PathBuf),
+    UnknownInputFormat(PathBuf),
+    Csv(csv::Error),
+    InvalidRow(String),
+    RowsFailed { failed: usize, total: usize },
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::PromptPay(err) => write!(f, "{}", err),
+            CliError::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+            CliError::UnknownImageFormat(path) => write!(
+                f,
+                "{}: unknown image format, use a .svg or .png extension",
+                path.display()
+            ),
+            CliError::UnknownInputFormat(path) => write!(
+                f,
+                "{}: unknown input format, use a .csv or .jsonl extension or --input-format",
+                path.display()
+            ),
+            CliError::Csv(err) => write!(f, "{}", err),
+            CliError::InvalidRow(message) => write!(f, "{}", message),
+            CliError::RowsFailed { failed, total } => {
+                write!(f, "{} of {} rows failed", failed, total)
+            }
+        }
+    }
+}
+
+impl From<PromptPayError> for CliError {
+    fn from(err: PromptPayError) -> Self {
+        CliError::PromptPay(err)
+    }
+}
+
+impl From<csv::Error> for CliError {
+    fn from(err: csv::Error) -> Self {
+        CliError::Csv(err)
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Generate(args) => generate(*args),
+        Command::Parse { payload } => parse(&payload),
+        Command::Verify { payload } => verify(&payload),
+        Command::Batch(args) => batch::run(args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+impl PayloadArgs {
+    /// Build the payload described by the arguments.
+    fn build(self) -> Result<PromptPayPayload, PromptPayError> {
+        let currency: Currency = self.currency.parse()?;
+        let mut builder = PromptPayBuilder::new(self.proxy.into_input()).currency(currency);
+
+        if let Some(amount) = &self.amount {
+            builder = builder.amount(Amount::parse_in(amount, currency)?);
+        }
+        if let Some(name) = self.merchant_name {
+            builder = builder.merchant_name(name);
+        }
+        if let Some(city) = self.merchant_city {
+            builder = builder.merchant_city(city);
+        }
+        if let Some(mcc) = self.mcc {
+            builder = builder.merchant_category_code(mcc);
+        }
+        if let Some(postal_code) = self.postal_code {
+            builder = builder.postal_code(postal_code);
+        }
+        if self.tip {
+            builder = builder.tip_or_convenience_fee(TipOrConvenienceFee::PromptForTip);
+        }
+
+        let mut additional_data = AdditionalData::new();
+        additional_data.bill_number = self.bill_number;
+        additional_data.reference_label = self.reference_label;
+        additional_data.terminal_label = self.terminal_label;
+        if !additional_data.is_empty() {
+            builder = builder.additional_data(additional_data);
+        }
+
+        builder.build()
+    }
+}
+
+fn generate(args: GenerateArgs) -> Result<(), CliError> {
+    let payload = args.payload.build()?;
+    let encoded = payload.encode()?;
+    println!("{}", encoded);
+
+    if let Some(path) = &args.output {
+        let contents = match extension(path).as_deref() {
+            Some("svg") => payload.to_svg(&SvgOptions::new())?.into_bytes(),
+            Some("png") if !args.frame && args.frame_fonts.is_empty() => {
+                payload.to_png(&PngOptions::new())?
+            }
+            Some("png") => {
+                let mut options = FrameOptions::default();
+                for (index, path) in args.frame_fonts.iter().enumerate() {
+                    options.fonts.insert(index, read(path)?.into());
+                }
+                payload.to_framed_png(&options)?
+            }
+            _ => return Err(CliError::UnknownImageFormat(path.clone())),
+        };
+        fs::write(path, contents).map_err(|err| CliError::Io(path.clone(), err))?;
+    }
+
+    if args.show {
+        print!("{}", payload.to_terminal(&TerminalOptions::new())?);
+    }
+    Ok(())
+}
+
+fn parse(payload: &str) -> Result<(), CliError> {
+    let payload = Utils::parse_payload(payload.trim())?;
+    for (name, value) in describe(&payload) {
+        println!("{:<24}{}", format!("{}:", name), value);
+    }
+    Ok(())
+}
+
+fn verify(payload: &str) -> Result<(), CliError> {
+    Utils::parse_payload(payload.trim())?;
+    println!("OK: CRC and structure are valid");
+    Ok(())
+}
+
+/// The fields of a decoded payload as name and value pairs, in payload order.
+fn describe(payload: &PromptPayPayload) -> Vec<(&'static str, String)> {
+    let mut fields = vec![(
+        "Point of initiation",
+        format!("{:?}", payload.point_of_initiation),
+    )];
+
+    match &payload.proxy {
+        Proxy::PhoneNumber(phone) => fields.push(("Phone number", phone.clone())),
+        Proxy::NationalID(id) => fields.push(("National ID", id.clone())),
+        Proxy::EWalletId(id) => fields.push(("E-wallet ID", id.clone())),
+        Proxy::BankAccount {
+            bank_code,
+            account_number,
+        } => {
+            fields.push(("Bank code", bank_code.clone()));
+            fields.push(("Account number", account_number.clone()));
+        }
+        Proxy::BillPayment {
+            biller_id,
+            reference1,
+            reference2,
+        } => {
+            fields.push(("Biller ID", biller_id.clone()));
+            fields.push(("Reference 1", reference1.clone()));
+            if let Some(reference2) = reference2 {
+                fields.push(("Reference 2", reference2.clone()));
+            }
+        }
+    }
+
+    let optional = |fields: &mut Vec<_>, name, value: &Option<String>| {
+        if let Some(value) = value {
+            fields.push((name, value.clone()));
+        }
+    };
+
+    optional(
+        &mut fields,
+        "Merchant category code",
+        &payload.merchant_category_code,
+    );
+    fields.push(("Currency", payload.currency.to_string()));
+    if let Some(amount) = payload.amount {
+        fields.push(("Amount", amount.format_in(payload.currency)));
+    }
+    match payload.tip_or_convenience_fee {
+        Some(TipOrConvenienceFee::PromptForTip) => fields.push(("Tip", "prompt".to_string())),
+        Some(TipOrConvenienceFee::FixedFee(fee)) => {
+            fields.push(("Convenience fee", fee.format_in(payload.currency)))
+        }
+        Some(TipOrConvenienceFee::PercentageFee(percentage)) => {
+            fields.push(("Convenience fee", format!("{}%", percentage)))
+        }
+        None => {}
+    }
+    fields.push(("Country", payload.country.clone()));
+    optional(&mut fields, "Merchant name", &payload.merchant_name);
+    optional(&mut fields, "Merchant city", &payload.merchant_city);
+    optional(&mut fields, "Postal code", &payload.postal_code);
+
+    if let Some(data) = &payload.additional_data {
+        optional(&mut fields, "Bill number", &data.bill_number);
+        optional(&mut fields, "Mobile number", &data.mobile_number);
+        optional(&mut fields, "Store label", &data.store_label);
+        optional(&mut fields, "Loyalty number", &data.loyalty_number);
+        optional(&mut fields, "Reference label", &data.reference_label);
+        optional(&mut fields, "Customer label", &data.customer_label);
+        optional(&mut fields, "Terminal label", &data.terminal_label);
+        optional(
+            &mut fields,
+            "Purpose of transaction",
+            &data.purpose_of_transaction,
+        );
+    }
+
+    if let Some(language) = &payload.merchant_information_language {
+        fields.push(("Language", language.language_preference.clone()));
+        fields.push(("Alternate merchant name", language.merchant_name.clone()));
+        optional(
+            &mut fields,
+            "Alternate merchant city",
+            &language.merchant_city,
+        );
+    }
+
+    fields
+}
+
+/// The lowercase extension of `path`, if it has one.
+fn extension(path: &Path) -> Option<String> {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase)
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, CliError> {
+    fs::read(path).map_err(|err| CliError::Io(path.to_path_buf(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{describe, Cli, Command};
+    use clap::{CommandFactory, Parser};
+    use prompt_pay::promptpay_utils::{InputType, Utils};
+
+    fn generate(args: &[&str]) -> String {
+        let cli = Cli::try_parse_from([&["promptpay", "generate"], args].concat()).unwrap();
+        match cli.command {
+            Command::Generate(args) => args.payload.build().unwrap().encode().unwrap(),
+            command => panic!("unexpected command {:?}", command),
+        }
+    }
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_generate_matches_utils() {
+        let payload = generate(&["--phone", "081-234-5678", "--amount", "123.45"]);
+        let expected =
+            Utils::generate_payload(InputType::PhoneNumber("0812345678".to_string()), 123.45);
+        assert_eq!(payload, expected.unwrap());
+        assert_eq!(
+            Utils::parse_payload(&payload)
+                .unwrap()
+                .amount
+                .unwrap()
+                .minor_units(),
+            12345
+        );
+    }
+
+    #[test]
+    fn test_generate_bill_payment() {
+        let payload = generate(&[
+            "--biller-id",
+            "123456789012100",
+            "--reference1",
+            "INV001",
+            "--merchant-name",
+            "SHOP",
+            "--bill-number",
+            "INV001",
+        ]);
+        let fields = describe(&Utils::parse_payload(&payload).unwrap());
+        assert!(fields.contains(&("Biller ID", "123456789012100".to_string())));
+        assert!(fields.contains(&("Merchant name", "SHOP".to_string())));
+        assert!(fields.contains(&("Bill number", "INV001".to_string())));
+    }
+
+    #[test]
+    fn test_generate_proxy_arguments() {
+        let parse =
+            |args: &[&str]| Cli::try_parse_from([&["promptpay", "generate"], args].concat());
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["--phone", "0812345678", "--national-id", "1234567890121"]).is_err());
+        assert!(parse(&["--account-number", "1234567890"]).is_err());
+        assert!(parse(&["--phone", "0812345678", "--bank-code", "004"]).is_err());
+        assert!(parse(&["--biller-id", "123456789012100"]).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+use crate::additional_data::AdditionalData;
+use crate::amount::{Amount, AmountPadding};
+use crate::currency::Currency;
+use crate::error::PromptPayError;
+use crate::language::MerchantInformationLanguage;
+use crate::payload::{PromptPayPayload, QrMode, TipOrConvenienceFee};
+use crate::promptpay_utils::{InputType, Utils};
+
+/// Builder for merchant-presented PromptPay payloads.
+///
+/// The account the payment goes to is the only required part and is passed to
+/// [`PromptPayBuilder::new`]; everything else is an optional chained call. Without an amount
+/// the payload is a static (reusable) QR code, with one it is a dynamic (one-time) QR code.
+///
+/// ```
+/// use prompt_pay::promptpay_utils::{Amount, InputType, PromptPayBuilder};
+///
+/// let payload = PromptPayBuilder::new(InputType::PhoneNumber("081-234-5678".to_string()))
+///     .amount("250.00".parse::<Amount>().unwrap())
+///     .merchant_name("SOMTAM SHOP")
+///     .merchant_city("BANGKOK")
+///     .build()
+///     .unwrap();
+/// assert_eq!(payload.merchant_name.as_deref(), Some("SOMTAM SHOP"));
+///
+/// let encoded = payload.encode().unwrap();
+/// assert!(encoded.contains("5406250.00"));
+/// ```
+pub struct PromptPayBuilder {
+    input: InputType,
+    mode: QrMode,
+    amount_padding: AmountPadding,
+    tip_or_convenience_fee: Option<TipOrConvenienceFee>,
+    currency: Currency,
+    country: Option<String>,
+    merchant_category_code: Option<String>,
+    merchant_name: Option<String>,
+    merchant_city: Option<String>,
+    postal_code: Option<String>,
+    additional_data: Option<AdditionalData>,
+    merchant_information_language: Option<MerchantInformationLanguage>,
+}
+
+impl PromptPayBuilder {
+    /// Start a payload paying into `input`, which is sanitized when the payload is built.
+    pub fn new(input: InputType) -> Self {
+        PromptPayBuilder {
+            input,
+            mode: QrMode::Static,
+            amount_padding: AmountPadding::None,
+            tip_or_convenience_fee: None,
+            currency: Currency::THB,
+            country: None,
+            merchant_category_code: None,
+            merchant_name: None,
+            merchant_city: None,
+            postal_code: None,
+            additional_data: None,
+            merchant_information_language: None,
+        }
+    }
+
+    /// Make the payload a dynamic QR code for `amount`.
+    pub fn amount(mut self, amount: Amount) -> Self {
+        self.mode = QrMode::Dynamic(amount);
+        self
+    }
+
+    /// Set whether the payload is static or dynamic, replacing any amount set before.
+    pub fn mode(mut self, mode: QrMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set how the amount is padded (tag 54).
+    pub fn amount_padding(mut self, padding: AmountPadding) -> Self {
+        self.amount_padding = padding;
+        self
+    }
+
+    /// Prompt for a tip or add a convenience fee (tags 55 to 57).
+    pub fn tip_or_convenience_fee(mut self, fee: TipOrConvenienceFee) -> Self {
+        self.tip_or_convenience_fee = Some(fee);
+        self
+    }
+
+    /// Set the transaction currency (tag 53), Thai Baht by default.
+    ///
+    /// The amount and any fixed fee are counted in minor units of this currency.
+    pub fn currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    /// Set the ISO 3166-1 alpha-2 country code of the merchant (tag 58), "TH" by default.
+    pub fn country(mut self, country: impl Into<String>) -> Self {
+        self.country = Some(country.into());
+        self
+    }
+
+    /// Set the merchant category code (tag 52).
+    pub fn merchant_category_code(mut self, mcc: impl Into<String>) -> Self {
+        self.merchant_category_code = Some(mcc.into());
+        self
+    }
+
+    /// Set the merchant name (tag 59).
+    pub fn merchant_name(mut self, name: impl Into<String>) -> Self {
+        self.merchant_name = Some(name.into());
+        self
+    }
+
+    /// Set the merchant city (tag 60).
+    pub fn merchant_city(mut self, city: impl Into<String>) -> Self {
+        self.merchant_city = Some(city.into());
+        self
+    }
+
+    /// Set the merchant postal code (tag 61).
+    pub fn postal_code(mut self, postal_code: impl Into<String>) -> Self {
+        self.postal_code = Some(postal_code.into());
+        self
+    }
+
+    /// Set the bill number, store, terminal and other reconciliation labels (tag 62).
+    pub fn additional_data(mut self, additional_data: AdditionalData) -> Self {
+        self.additional_data = Some(additional_data);
+        self
+    }
+
+    /// Set the merchant name and city in a second language such as Thai (tag 64).
+    pub fn merchant_information_language(mut self, language: MerchantInformationLanguage) -> Self {
+        self.merchant_information_language = Some(language);
+        self
+    }
+
+    /// Sanitize the input and assemble the payload.
+    ///
+    /// # Returns
+    /// The payload, or an error if the input or any of the fields is invalid. A payload
+    /// returned by this function always encodes successfully.
+    pub fn build(self) -> Result<PromptPayPayload, PromptPayError> {
+        let proxy = Utils::sanitize_input(self.input)?;
+
+        let mut payload = PromptPayPayload::new(proxy, self.mode);
+        payload.amount_padding = self.amount_padding;
+        payload.tip_or_convenience_fee = self.tip_or_convenience_fee;
+        payload.currency = self.currency;
+        if let Some(country) = self.country {
+            payload.country = country;
+        }
+        payload.merchant_category_code = self.merchant_category_code;
+        payload.merchant_name = self.merchant_name;
+        payload.merchant_city = self.merchant_city;
+        payload.postal_code = self.postal_code;
+        payload.additional_data = self.additional_data;
+        payload.merchant_information_language = self.merchant_information_language;
+
+        // Report invalid fields now rather than when the payload is first encoded
+        payload.encode()?;
+        Ok(payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PromptPayBuilder;
+    use crate::additional_data::AdditionalData;
+    use crate::amount::Amount;
+    use crate::error::{Field, PromptPayError};
+    use crate::payload::{PointOfInitiation, PromptPayPayload, Proxy};
+    use crate::promptpay_utils::{InputType, Utils};
+
+    #[test]
+    fn test_builder_static_payload() {
+        let payload = PromptPayBuilder::new(InputType::PhoneNumber("0812345678".to_string()))
+            .build()
+            .unwrap();
+        assert_eq!(payload.point_of_initiation, PointOfInitiation::Static);
+        assert_eq!(
+            payload.proxy,
+            Proxy::PhoneNumber("0066812345678".to_string())
+        );
+        assert_eq!(payload.amount, None);
+    }
+
+    #[test]
+    fn test_builder_matches_generate_payload() {
+        let built = PromptPayBuilder::new(InputType::PhoneNumber("0812345678".to_string()))
+            .amount(Amount::from_minor_units(12_345).unwrap())
+            .build()
+            .unwrap();
+        let generated =
+            Utils::generate_payload(InputType::PhoneNumber("0812345678".to_string()), 123.45)
+                .unwrap();
+        assert_eq!(built.encode().unwrap(), generated);
+    }
+
+    #[test]
+    fn test_builder_optional_fields_round_trip() {
+        let payload = PromptPayBuilder::new(InputType::BillPayment {
+            biller_id: "010753600031508".to_string(),
+            reference1: "INV001".to_string(),
+            reference2: Some("BRANCH12".to_string()),
+        })
+        .amount(Amount::from_minor_units(50_000).unwrap())
+        .merchant_category_code("5812")
+        .merchant_name("SOMTAM SHOP")
+        .merchant_city("BANGKOK")
+        .additional_data(AdditionalData::new().terminal_label("POS3"))
+        .build()
+        .unwrap();
+
+        let encoded = payload.encode().unwrap();
+        assert_eq!(PromptPayPayload::parse(&encoded).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_builder_reports_invalid_fields() {
+        let result = PromptPayBuilder::new(InputType::NationalID("1234567890121".to_string()))
+            .merchant_name("X".repeat(26))
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            PromptPayError::LengthOutOfRange {
+                field: Field::MerchantName,
+                min: 1,
+                max: 25,
+                actual: 26
+            }
+        );
+    }
+}
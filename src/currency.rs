@@ -0,0 +1,295 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Field, PromptPayError};
+
+/// An ISO 4217 currency: its alphabetic code, the numeric code carried in the transaction
+/// currency field (tag 53), and the number of minor-unit digits its amounts are written with.
+///
+/// ```
+/// use prompt_pay::currency::Currency;
+///
+/// let yen: Currency = "JPY".parse().unwrap();
+/// assert_eq!(yen.numeric_code(), "392");
+/// assert_eq!(yen.minor_units(), 0);
+/// assert_eq!(Currency::from_numeric("764"), Some(Currency::THB));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Currency {
+    alpha_code: &'static str,
+    numeric_code: &'static str,
+    minor_units: u8,
+}
+
+impl Currency {
+    /// Thai Baht, the currency of PromptPay payloads.
+    pub const THB: Currency = Currency::new("THB", "764", 2);
+
+    const fn new(alpha_code: &'static str, numeric_code: &'static str, minor_units: u8) -> Self {
+        Currency {
+            alpha_code,
+            numeric_code,
+            minor_units,
+        }
+    }
+
+    /// Look up a currency by its three-letter code, e.g. "USD", ignoring case.
+    pub fn from_alpha(code: &str) -> Option<Self> {
+        CURRENCIES
+            .iter()
+            .find(|currency| currency.alpha_code.eq_ignore_ascii_case(code))
+            .copied()
+    }
+
+    /// Look up a currency by its three-digit numeric code, e.g. "840".
+    pub fn from_numeric(code: &str) -> Option<Self> {
+        CURRENCIES
+            .iter()
+            .find(|currency| currency.numeric_code == code)
+            .copied()
+    }
+
+    /// The three-letter code, e.g. "THB".
+    pub fn alpha_code(&self) -> &'static str {
+        self.alpha_code
+    }
+
+    /// The three-digit numeric code, e.g. "764".
+    pub fn numeric_code(&self) -> &'static str {
+        self.numeric_code
+    }
+
+    /// The number of decimals amounts are written with, e.g. 2 for Baht and 0 for Yen.
+    pub fn minor_units(&self) -> u8 {
+        self.minor_units
+    }
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Currency::THB
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.alpha_code)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Currency {
+    /// Serialize the currency as its three-letter code, e.g. "THB".
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.alpha_code)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Currency {
+    /// Deserialize a currency from its three-letter or three-digit code.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        code.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl FromStr for Currency {
+    type Err = PromptPayError;
+
+    /// Parse a currency from either its three-letter or its three-digit code.
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        let code = code.trim();
+        Currency::from_alpha(code)
+            .or_else(|| Currency::from_numeric(code))
+            .ok_or_else(|| PromptPayError::InvalidValue {
+                field: Field::Currency,
+                value: code.to_string(),
+            })
+    }
+}
+
+/// Active ISO 4217 currencies, sorted by alphabetic code. Precious metals and other codes
+/// without minor units are left out since they cannot carry a transaction amount.
+const CURRENCIES: &[Currency] = &[
+    Currency::new("AED", "784", 2),
+    Currency::new("AFN", "971", 2),
+    Currency::new("ALL", "008", 2),
+    Currency::new("AMD", "051", 2),
+    Currency::new("ANG", "532", 2),
+    Currency::new("AOA", "973", 2),
+    Currency::new("ARS", "032", 2),
+    Currency::new("AUD", "036", 2),
+    Currency::new("AWG", "533", 2),
+    Currency::new("AZN", "944", 2),
+    Currency::new("BAM", "977", 2),
+    Currency::new("BBD", "052", 2),
+    Currency::new("BDT", "050", 2),
+    Currency::new("BGN", "975", 2),
+    Currency::new("BHD", "048", 3),
+    Currency::new("BIF", "108", 0),
+    Currency::new("BMD", "060", 2),
+    Currency::new("BND", "096", 2),
+    Currency::new("BOB", "068", 2),
+    Currency::new("BOV", "984", 2),
+    Currency::new("BRL", "986", 2),
+    Currency::new("BSD", "044", 2),
+    Currency::new("BTN", "064", 2),
+    Currency::new("BWP", "072", 2),
+    Currency::new("BYN", "933", 2),
+    Currency::new("BZD", "084", 2),
+    Currency::new("CAD", "124", 2),
+    Currency::new("CDF", "976", 2),
+    Currency::new("CHE", "947", 2),
+    Currency::new("CHF", "756", 2),
+    Currency::new("CHW", "948", 2),
+    Currency::new("CLF", "990", 4),
+    Currency::new("CLP", "152", 0),
+    Currency::new("CNY", "156", 2),
+    Currency::new("COP", "170", 2),
+    Currency::new("COU", "970", 2),
+    Currency::new("CRC", "188", 2),
+    Currency::new("CUP", "192", 2),
+    Currency::new("CVE", "132", 2),
+    Currency::new("CZK", "203", 2),
+    Currency::new("DJF", "262", 0),
+    Currency::new("DKK", "208", 2),
+    Currency::new("DOP", "214", 2),
+    Currency::new("DZD", "012", 2),
+    Currency::new("EGP", "818", 2),
+    Currency::new("ERN", "232", 2),
+    Currency::new("ETB", "230", 2),
+    Currency::new("EUR", "978", 2),
+    Currency::new("FJD", "242", 2),
+    Currency::new("FKP", "238", 2),
+    Currency::new("GBP", "826", 2),
+    Currency::new("GEL", "981", 2),
+    Currency::new("GHS", "936", 2),
+    Currency::new("GIP", "292", 2),
+    Currency::new("GMD", "270", 2),
+    Currency::new("GNF", "324", 0),
+    Currency::new("GTQ", "320", 2),
+    Currency::new("GYD", "328", 2),
+    Currency::new("HKD", "344", 2),
+    Currency::new("HNL", "340", 2),
+    Currency::new("HTG", "332", 2),
+    Currency::new("HUF", "348", 2),
+    Currency::new("IDR", "360", 2),
+    Currency::new("ILS", "376", 2),
+    Currency::new("INR", "356", 2),
+    Currency::new("IQD", "368", 3),
+    Currency::new("IRR", "364", 2),
+    Currency::new("ISK", "352", 0),
+    Currency::new("JMD", "388", 2),
+    Currency::new("JOD", "400", 3),
+    Currency::new("JPY", "392", 0),
+    Currency::new("KES", "404", 2),
+    Currency::new("KGS", "417", 2),
+    Currency::new("KHR", "116", 2),
+    Currency::new("KMF", "174", 0),
+    Currency::new("KPW", "408", 2),
+    Currency::new("KRW", "410", 0),
+    Currency::new("KWD", "414", 3),
+    Currency::new("KYD", "136", 2),
+    Currency::new("KZT", "398", 2),
+    Currency::new("LAK", "418", 2),
+    Currency::new("LBP", "422", 2),
+    Currency::new("LKR", "144", 2),
+    Currency::new("LRD", "430", 2),
+    Currency::new("LSL", "426", 2),
+    Currency::new("LYD", "434", 3),
+    Currency::new("MAD", "504", 2),
+    Currency::new("MDL", "498", 2),
+    Currency::new("MGA", "969", 2),
+    Currency::new("MKD", "807", 2),
+    Currency::new("MMK", "104", 2),
+    Currency::new("MNT", "496", 2),
+    Currency::new("MOP", "446", 2),
+    Currency::new("MRU", "929", 2),
+    Currency::new("MUR", "480", 2),
+    Currency::new("MVR", "462", 2),
+    Currency::new("MWK", "454", 2),
+    Currency::new("MXN", "484", 2),
+    Currency::new("MXV", "979", 2),
+    Currency::new("MYR", "458", 2),
+    Currency::new("MZN", "943", 2),
+    Currency::new("NAD", "516", 2),
+    Currency::new("NGN", "566", 2),
+    Currency::new("NIO", "558", 2),
+    Currency::new("NOK", "578", 2),
+    Currency::new("NPR", "524", 2),
+    Currency::new("NZD", "554", 2),
+    Currency::new("OMR", "512", 3),
+    Currency::new("PAB", "590", 2),
+    Currency::new("PEN", "604", 2),
+    Currency::new("PGK", "598", 2),
+    Currency::new("PHP", "608", 2),
+    Currency::new("PKR", "586", 2),
+    Currency::new("PLN", "985", 2),
+    Currency::new("PYG", "600", 0),
+    Currency::new("QAR", "634", 2),
+    Currency::new("RON", "946", 2),
+    Currency::new("RSD", "941", 2),
+    Currency::new("RUB", "643", 2),
+    Currency::new("RWF", "646", 0),
+    Currency::new("SAR", "682", 2),
+    Currency::new("SBD", "090", 2),
+    Currency::new("SCR", "690", 2),
+    Currency::new("SDG", "938", 2),
+    Currency::new("SEK", "752", 2),
+    Currency::new("SGD", "702", 2),
+    Currency::new("SHP", "654", 2),
+    Currency::new("SLE", "925", 2),
+    Currency::new("SOS", "706", 2),
+    Currency::new("SRD", "968", 2),
+    Currency::new("SSP", "728", 2),
+    Currency::new("STN", "930", 2),
+    Currency::new("SVC", "222", 2),
+    Currency::new("SYP", "760", 2),
+    Currency::new("SZL", "748", 2),
+    Currency::new("THB", "764", 2),
+    Currency::new("TJS", "972", 2),
+    Currency::new("TMT", "934", 2),
+    Currency::new("TND", "788", 3),
+    Currency::new("TOP", "776", 2),
+    Currency::new("TRY", "949", 2),
+    Currency::new("TTD", "780", 2),
+    Currency::new("TWD", "901", 2),
+    Currency::new("TZS", "834", 2),
+    Currency::new("UAH", "980", 2),
+    Currency::new("UGX", "800", 0),
+    Currency::new("USD", "840", 2),
+    Currency::new("USN", "997", 2),
+    Currency::new("UYI", "940", 0),
+    Currency::new("UYU", "858", 2),
+    Currency::new("UYW", "927", 4),
+    Currency::new("UZS", "860", 2),
+    Currency::new("VED", "926", 2),
+    Currency::new("VES", "928", 2),
+    Currency::new("VND", "704", 0),
+    Currency::new("VUV", "548", 0),
+    Currency::new("WST", "882", 2),
+    Currency::new("XAF", "950", 0),
+    Currency::new("XCD", "951", 2),
+    Currency::new("XOF", "952", 0),
+    Currency::new("XPF", "953", 0),
+    Currency::new("YER", "886", 2),
+    Currency::new("ZAR", "710", 2),
+    Currency::new("ZMW", "967", 2),
+    Currency::new("ZWG", "924", 2),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::Currency;
+
+    #[test]
+    fn test_currency_lookup() {
+        assert_eq!(Currency::from_alpha("thb"), Some(Currency::THB));
+        assert_eq!(Currency::from_numeric("764"), Some(Currency::THB));
+        assert_eq!(Currency::from_alpha("KWD").unwrap().minor_units(), 3);
+        assert_eq!(Currency::from_numeric("392").unwrap().alpha_code(), "JPY");
+        assert_eq!("840".parse::<Currency>().unwrap().alpha_code(), "USD");
+        assert!("XYZ".parse::<Currency>().is_err());
+    }
+}
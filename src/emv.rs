@@ -0,0 +1,304 @@
+use crate::error::{Field, PromptPayError};
+
+/// Payload Format Indicator, always the first data object.
+pub const PAYLOAD_FORMAT_INDICATOR: &str = "00";
+/// Point of Initiation Method (static or dynamic QR).
+pub const POINT_OF_INITIATION: &str = "01";
+/// Merchant Category Code (ISO 18245).
+pub const MERCHANT_CATEGORY_CODE: &str = "52";
+/// Transaction Currency (ISO 4217 numeric code).
+pub const TRANSACTION_CURRENCY: &str = "53";
+/// Transaction Amount.
+pub const TRANSACTION_AMOUNT: &str = "54";
+/// Tip or Convenience Indicator.
+pub const TIP_OR_CONVENIENCE_INDICATOR: &str = "55";
+/// Value of Convenience Fee Fixed.
+pub const CONVENIENCE_FEE_FIXED: &str = "56";
+/// Value of Convenience Fee Percentage.
+pub const CONVENIENCE_FEE_PERCENTAGE: &str = "57";
+/// Country Code (ISO 3166-1 alpha-2).
+pub const COUNTRY_CODE: &str = "58";
+/// Merchant Name.
+pub const MERCHANT_NAME: &str = "59";
+/// Merchant City.
+pub const MERCHANT_CITY: &str = "60";
+/// Postal Code.
+pub const POSTAL_CODE: &str = "61";
+/// Additional Data Field Template.
+pub const ADDITIONAL_DATA_FIELD_TEMPLATE: &str = "62";
+/// Merchant Information—Language Template (alternate-language name and city).
+pub const MERCHANT_INFORMATION_LANGUAGE_TEMPLATE: &str = "64";
+/// Cyclic Redundancy Check, always the last data object.
+pub const CRC: &str = "63";
+
+/// The longest value that fits in the two-digit length field.
+///
+/// Lengths count characters rather than bytes, so a Thai value in the language template
+/// may take up to three times as many bytes.
+pub const MAX_VALUE_LENGTH: usize = 99;
+
+/// A single EMVCo data object: a two-digit tag and its value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataObject {
+    tag: String,
+    value: Value,
+}
+
+/// The value of a data object, either a plain string or a nested template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Primitive(String),
+    Template(Vec<DataObject>),
+}
+
+impl DataObject {
+    /// Create a data object holding a plain string value.
+    pub fn primitive(tag: &str, value: impl Into<String>) -> Self {
+        DataObject {
+            tag: tag.to_string(),
+            value: Value::Primitive(value.into()),
+        }
+    }
+
+    /// Create a data object whose value is a nested list of data objects.
+    pub fn template(tag: &str, children: Vec<DataObject>) -> Self {
+        DataObject {
+            tag: tag.to_string(),
+            value: Value::Template(children),
+        }
+    }
+
+    /// The two-digit tag of this data object.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// The value of this data object.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// The value as a string, or `None` if this object was built as a template.
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            Value::Primitive(value) => Some(value),
+            Value::Template(_) => None,
+        }
+    }
+
+    /// Interpret the value of this data object as a nested template.
+    ///
+    /// Decoded payloads only contain primitive values, since the wire format does not
+    /// say which tags are templates; this decodes such a value on demand.
+    ///
+    /// # Returns
+    /// The nested data objects, or an error if the value is not a valid TLV sequence.
+    pub fn children(&self) -> Result<Vec<DataObject>, PromptPayError> {
+        match &self.value {
+            Value::Primitive(value) => decode(value),
+            Value::Template(children) => Ok(children.clone()),
+        }
+    }
+
+    /// Serialize this data object as tag, two-digit length and value, where the length is
+    /// the number of characters in the value.
+    ///
+    /// # Returns
+    /// The encoded data object, or an error if the tag is not two digits or the
+    /// value does not fit in the length field.
+    pub fn encode(&self) -> Result<String, PromptPayError> {
+        if self.tag.len() != 2 || !self.tag.chars().all(|c| c.is_ascii_digit()) {
+            return Err(PromptPayError::InvalidTag(self.tag.clone()));
+        }
+
+        let value = match &self.value {
+            Value::Primitive(value) => value.clone(),
+            Value::Template(children) => encode(children)?,
+        };
+
+        let length = value.chars().count();
+        if length > MAX_VALUE_LENGTH {
+            return Err(PromptPayError::ValueTooLong {
+                tag: self.tag.clone(),
+                length,
+                max: MAX_VALUE_LENGTH,
+            });
+        }
+
+        Ok(format!("{}{:02}{}", self.tag, length, value))
+    }
+}
+
+/// Serialize a list of data objects in the order given.
+///
+/// # Parameters
+/// - `objects`: The data objects to serialize.
+///
+/// # Returns
+/// The concatenated encoding of every data object, or the first encoding error.
+pub fn encode(objects: &[DataObject]) -> Result<String, PromptPayError> {
+    objects.iter().map(DataObject::encode).collect()
+}
+
+/// Decode a TLV string into its top-level data objects.
+///
+/// Every value is returned as [`Value::Primitive`]; use [`DataObject::children`] to
+/// decode the nested templates.
+///
+/// # Parameters
+/// - `input`: The encoded data objects.
+///
+/// # Returns
+/// The data objects in the order they appear, or an error if the input is truncated
+/// or a tag or length field is malformed.
+pub fn decode(input: &str) -> Result<Vec<DataObject>, PromptPayError> {
+    let mut objects = Vec::new();
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        let (tag, length) = match (rest.get(0..2), rest.get(2..4)) {
+            (Some(tag), Some(length)) => (tag, length),
+            _ => return Err(PromptPayError::TruncatedDataObject { tag: None }),
+        };
+
+        if !tag.chars().all(|c| c.is_ascii_digit()) {
+            return Err(PromptPayError::InvalidTag(tag.to_string()));
+        }
+        if !length.chars().all(|c| c.is_ascii_digit()) {
+            return Err(PromptPayError::InvalidDataObjectLength {
+                tag: tag.to_string(),
+                length: length.to_string(),
+            });
+        }
+        let length: usize = length.parse().unwrap_or_default();
+
+        // The length counts characters, so find the byte offset where the value ends
+        let value_and_rest = &rest[4..];
+        let end = match value_and_rest.char_indices().nth(length) {
+            Some((end, _)) => end,
+            None if value_and_rest.chars().count() == length => value_and_rest.len(),
+            None => {
+                return Err(PromptPayError::TruncatedDataObject {
+                    tag: Some(tag.to_string()),
+                })
+            }
+        };
+        objects.push(DataObject::primitive(tag, &value_and_rest[..end]));
+        rest = &value_and_rest[end..];
+    }
+
+    Ok(objects)
+}
+
+/// Check a free-text field: 1 to `max_length` printable ASCII characters, the character set
+/// EMVCo allows for fields outside the alternate-language template.
+pub(crate) fn check_text(
+    field: Field,
+    value: &str,
+    max_length: usize,
+) -> Result<(), PromptPayError> {
+    if let Some(character) = value
+        .chars()
+        .find(|c| !c.is_ascii() || c.is_ascii_control())
+    {
+        return Err(PromptPayError::InvalidCharacter { field, character });
+    }
+    if value.is_empty() || value.len() > max_length {
+        return Err(PromptPayError::LengthOutOfRange {
+            field,
+            min: 1,
+            max: max_length,
+            actual: value.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Check a free-text field of the language template: 1 to `max_length` characters of any
+/// script, counted as Unicode code points, without control characters.
+pub(crate) fn check_unicode_text(
+    field: Field,
+    value: &str,
+    max_length: usize,
+) -> Result<(), PromptPayError> {
+    if let Some(character) = value.chars().find(|c| c.is_control()) {
+        return Err(PromptPayError::InvalidCharacter { field, character });
+    }
+    let length = value.chars().count();
+    if length == 0 || length > max_length {
+        return Err(PromptPayError::LengthOutOfRange {
+            field,
+            min: 1,
+            max: max_length,
+            actual: length,
+        });
+    }
+    Ok(())
+}
+
+/// Find the first data object with the given tag.
+pub fn find<'a>(objects: &'a [DataObject], tag: &str) -> Option<&'a DataObject> {
+    objects.iter().find(|object| object.tag == tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode, find, DataObject};
+
+    #[test]
+    fn test_encode_computes_lengths() {
+        let objects = vec![
+            DataObject::primitive("00", "01"),
+            DataObject::template(
+                "29",
+                vec![
+                    DataObject::primitive("00", "A000000677010111"),
+                    DataObject::primitive("01", "0066812345678"),
+                ],
+            ),
+        ];
+        let result = encode(&objects).unwrap();
+        assert_eq!(result, "00020129370016A00000067701011101130066812345678");
+    }
+
+    #[test]
+    fn test_encode_rejects_long_value() {
+        let object = DataObject::primitive("62", "X".repeat(100));
+        assert!(object.encode().is_err());
+    }
+
+    #[test]
+    fn test_encode_rejects_invalid_tag() {
+        let object = DataObject::primitive("6", "X");
+        assert!(object.encode().is_err());
+    }
+
+    #[test]
+    fn test_decode_round_trip() {
+        let input = "00020101021129370016A000000677010111011300668123456785802TH";
+        let objects = decode(input).unwrap();
+        assert_eq!(objects.len(), 4);
+        assert_eq!(encode(&objects).unwrap(), input);
+
+        let merchant = find(&objects, "29").unwrap().children().unwrap();
+        assert_eq!(merchant[1].as_str(), Some("0066812345678"));
+    }
+
+    #[test]
+    fn test_utf8_lengths_count_characters() {
+        let object = DataObject::primitive("01", "ส้มตำ");
+        let encoded = object.encode().unwrap();
+        assert_eq!(encoded, "0105ส้มตำ");
+
+        let objects = decode(&format!("{}0202TH", encoded)).unwrap();
+        assert_eq!(objects[0].as_str(), Some("ส้มตำ"));
+        assert_eq!(objects[1].as_str(), Some("TH"));
+        assert!(decode("0106ส้มตำ").is_err());
+    }
+
+    #[test]
+    fn test_decode_truncated() {
+        assert!(decode("000201010").is_err());
+        assert!(decode("5802T").is_err());
+    }
+}
@@ -0,0 +1,301 @@
+use std::error::Error;
+use std::fmt;
+
+/// The payload field an error refers to.
+///
+/// New fields may be added in minor releases, so matches need a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Field {
+    PhoneNumber,
+    NationalID,
+    EWalletId,
+    BankCode,
+    BankAccount,
+    BillerId,
+    Reference1,
+    Reference2,
+    Amount,
+    TipOrConvenienceIndicator,
+    ConvenienceFee,
+    ConvenienceFeePercentage,
+    MerchantCategoryCode,
+    MerchantName,
+    MerchantCity,
+    PostalCode,
+    BillNumber,
+    MobileNumber,
+    StoreLabel,
+    LoyaltyNumber,
+    ReferenceLabel,
+    CustomerLabel,
+    TerminalLabel,
+    PurposeOfTransaction,
+    LanguagePreference,
+    AlternateMerchantName,
+    AlternateMerchantCity,
+    PayloadFormatIndicator,
+    PointOfInitiation,
+    MerchantAccount,
+    ApplicationId,
+    ProxyId,
+    Currency,
+    CountryCode,
+    Crc,
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Field::PhoneNumber => "phone number",
+            Field::NationalID => "national ID",
+            Field::EWalletId => "e-wallet ID",
+            Field::BankCode => "bank code",
+            Field::BankAccount => "bank account number",
+            Field::BillerId => "biller ID",
+            Field::Reference1 => "bill payment reference 1",
+            Field::Reference2 => "bill payment reference 2",
+            Field::Amount => "amount",
+            Field::TipOrConvenienceIndicator => "tip or convenience indicator",
+            Field::ConvenienceFee => "convenience fee",
+            Field::ConvenienceFeePercentage => "convenience fee percentage",
+            Field::MerchantCategoryCode => "merchant category code",
+            Field::MerchantName => "merchant name",
+            Field::MerchantCity => "merchant city",
+            Field::PostalCode => "postal code",
+            Field::BillNumber => "bill number",
+            Field::MobileNumber => "mobile number",
+            Field::StoreLabel => "store label",
+            Field::LoyaltyNumber => "loyalty number",
+            Field::ReferenceLabel => "reference label",
+            Field::CustomerLabel => "customer label",
+            Field::TerminalLabel => "terminal label",
+            Field::PurposeOfTransaction => "purpose of transaction",
+            Field::LanguagePreference => "language preference",
+            Field::AlternateMerchantName => "alternate language merchant name",
+            Field::AlternateMerchantCity => "alternate language merchant city",
+            Field::PayloadFormatIndicator => "payload format indicator",
+            Field::PointOfInitiation => "point of initiation",
+            Field::MerchantAccount => "merchant account information",
+            Field::ApplicationId => "application ID",
+            Field::ProxyId => "proxy ID",
+            Field::Currency => "transaction currency",
+            Field::CountryCode => "country code",
+            Field::Crc => "CRC",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Errors returned when generating, sanitizing or parsing PromptPay payloads.
+///
+/// New variants may be added in minor releases, so matches need a wildcard arm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PromptPayError {
+    /// A field does not have the exact number of characters it requires.
+    InvalidLength {
+        field: Field,
+        expected: usize,
+        actual: usize,
+    },
+    /// A field is shorter or longer than it is allowed to be.
+    LengthOutOfRange {
+        field: Field,
+        min: usize,
+        max: usize,
+        actual: usize,
+    },
+    /// A field contains a character it does not allow.
+    InvalidCharacter { field: Field, character: char },
+    /// The check digit of a national ID or tax ID does not match the other digits.
+    InvalidCheckDigit {
+        field: Field,
+        expected: char,
+        actual: char,
+    },
+    /// A field holds a value that is not allowed, e.g. an unknown application ID.
+    InvalidValue { field: Field, value: String },
+    /// A required field is missing.
+    MissingField(Field),
+    /// The phone number is not a Thai mobile number (06, 08 or 09).
+    NotMobileNumber(String),
+    /// The bank code does not belong to a known Thai bank.
+    UnknownBankCode(String),
+    /// The amount is negative.
+    NegativeAmount,
+    /// The amount is NaN or infinite.
+    NonFiniteAmount,
+    /// The amount is larger than the payload can carry.
+    AmountTooLarge,
+    /// The amount has more than two decimal places.
+    AmountTooPrecise,
+    /// A dynamic QR code was encoded without an amount.
+    MissingAmount,
+    /// The convenience fee tags do not match the tip or convenience indicator.
+    InconsistentConvenienceFee,
+    /// The CRC at the end of a payload does not match its contents.
+    CrcMismatch { expected: String, actual: String },
+    /// A data object tag is not two digits.
+    InvalidTag(String),
+    /// A data object length field is not two digits.
+    InvalidDataObjectLength { tag: String, length: String },
+    /// The input ends in the middle of a data object; `tag` is `None` if even the tag and
+    /// length are cut off.
+    TruncatedDataObject { tag: Option<String> },
+    /// The payload is too long to fit in a QR code.
+    PayloadTooLong,
+    /// The font data given for rendering text is not a TrueType or OpenType font.
+    InvalidFont,
+    /// None of the fonts given for rendering text has a glyph for the character.
+    MissingGlyph(char),
+    /// The rendered image could not be encoded.
+    ImageEncoding(String),
+    /// The rendered image would be too large to allocate, to encode as PNG or to measure in
+    /// modules or pixels.
+    ImageTooLarge,
+    /// A data object value does not fit in the two-digit length field.
+    ValueTooLong {
+        tag: String,
+        length: usize,
+        max: usize,
+    },
+}
+
+impl fmt::Display for PromptPayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PromptPayError::InvalidLength {
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Invalid {} length: expected {} characters, found {}",
+                field, expected, actual
+            ),
+            PromptPayError::LengthOutOfRange {
+                field,
+                min,
+                max,
+                actual,
+            } => write!(
+                f,
+                "Invalid {} length: expected {} to {} characters, found {}",
+                field, min, max, actual
+            ),
+            PromptPayError::InvalidCharacter { field, character } => {
+                write!(f, "Invalid character {:?} in {}", character, field)
+            }
+            PromptPayError::InvalidCheckDigit {
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Invalid {} check digit: expected {}, found {}",
+                field, expected, actual
+            ),
+            PromptPayError::InvalidValue { field, value } => {
+                write!(f, "Invalid {} \"{}\"", field, value)
+            }
+            PromptPayError::MissingField(field) => write!(f, "Missing {}", field),
+            PromptPayError::NotMobileNumber(number) => write!(
+                f,
+                "\"{}\" is not a Thai mobile number starting with 06, 08 or 09",
+                number
+            ),
+            PromptPayError::UnknownBankCode(code) => write!(f, "Unknown bank code \"{}\"", code),
+            PromptPayError::NegativeAmount => f.write_str("Amount must not be negative"),
+            PromptPayError::NonFiniteAmount => f.write_str("Amount must be a finite number"),
+            PromptPayError::AmountTooLarge => f.write_str("Amount is too large"),
+            PromptPayError::AmountTooPrecise => {
+                f.write_str("Amount has more than two decimal places")
+            }
+            PromptPayError::MissingAmount => f.write_str("A dynamic QR code requires an amount"),
+            PromptPayError::InconsistentConvenienceFee => {
+                f.write_str("Convenience fee tags do not match the tip or convenience indicator")
+            }
+            PromptPayError::CrcMismatch { expected, actual } => {
+                write!(f, "CRC mismatch: expected {}, found {}", expected, actual)
+            }
+            PromptPayError::InvalidTag(tag) => write!(f, "Invalid data object tag \"{}\"", tag),
+            PromptPayError::InvalidDataObjectLength { tag, length } => {
+                write!(f, "Invalid length \"{}\" for data object {}", length, tag)
+            }
+            PromptPayError::TruncatedDataObject { tag: Some(tag) } => {
+                write!(f, "Data object {} is truncated", tag)
+            }
+            PromptPayError::TruncatedDataObject { tag: None } => {
+                f.write_str("Truncated data object header")
+            }
+            PromptPayError::PayloadTooLong => f.write_str("Payload is too long for a QR code"),
+            PromptPayError::InvalidFont => f.write_str("Font data is not a valid font"),
+            PromptPayError::MissingGlyph(character) => {
+                write!(f, "No font has a glyph for {:?}", character)
+            }
+            PromptPayError::ImageEncoding(message) => {
+                write!(f, "Failed to encode image: {}", message)
+            }
+            PromptPayError::ImageTooLarge => f.write_str("Image is too large to render"),
+            PromptPayError::ValueTooLong { tag, length, max } => write!(
+                f,
+                "Value of data object {} is {} characters long, the maximum is {}",
+                tag, length, max
+            ),
+        }
+    }
+}
+
+impl Error for PromptPayError {}
+
+/// Check that a field is exactly `length` ASCII digits.
+pub(crate) fn check_digits(field: Field, value: &str, length: usize) -> Result<(), PromptPayError> {
+    if let Some(character) = value.chars().find(|c| !c.is_ascii_digit()) {
+        return Err(PromptPayError::InvalidCharacter { field, character });
+    }
+    if value.len() != length {
+        return Err(PromptPayError::InvalidLength {
+            field,
+            expected: length,
+            actual: value.len(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_digits, Field, PromptPayError};
+
+    #[test]
+    fn test_error_display() {
+        let error = PromptPayError::InvalidLength {
+            field: Field::NationalID,
+            expected: 13,
+            actual: 8,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Invalid national ID length: expected 13 characters, found 8"
+        );
+
+        let error = PromptPayError::CrcMismatch {
+            expected: "8242".to_string(),
+            actual: "0000".to_string(),
+        };
+        assert_eq!(error.to_string(), "CRC mismatch: expected 8242, found 0000");
+    }
+
+    #[test]
+    fn test_check_digits() {
+        assert!(check_digits(Field::BillerId, "010753600031508", 15).is_ok());
+        assert_eq!(
+            check_digits(Field::BillerId, "01075360003150X", 15),
+            Err(PromptPayError::InvalidCharacter {
+                field: Field::BillerId,
+                character: 'X'
+            })
+        );
+    }
+}
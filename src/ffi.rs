@@ -0,0 +1,436 @@
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use crate::amount::Amount;
+use crate::error::{Field, PromptPayError};
+use crate::payload::QrMode;
+use crate::promptpay_utils::{InputType, Utils};
+
+/// `input_type` of `promptpay_generate` for a mobile number.
+pub const PROMPTPAY_INPUT_PHONE_NUMBER: u32 = 0;
+/// `input_type` of `promptpay_generate` for a 13-digit national ID or tax ID.
+pub const PROMPTPAY_INPUT_NATIONAL_ID: u32 = 1;
+/// `input_type` of `promptpay_generate` for a 15-digit e-wallet ID.
+pub const PROMPTPAY_INPUT_EWALLET_ID: u32 = 2;
+
+/// Result of every fallible C function. On failure, `promptpay_last_error` describes the
+/// error in detail.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptPayStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = 1,
+    /// A string argument was not valid UTF-8.
+    InvalidUtf8 = 2,
+    /// `input_type` is not one of the `PROMPTPAY_INPUT_*` constants.
+    UnknownInputType = 3,
+    /// The proxy, bank account or bill payment fields are invalid.
+    InvalidInput = 4,
+    /// The amount is malformed, negative, too large or too precise.
+    InvalidAmount = 5,
+    /// The payload string is not a well-formed PromptPay payload.
+    InvalidPayload = 6,
+    /// The CRC of the payload string does not match its contents.
+    CrcMismatch = 7,
+    /// An unexpected internal error; please report it.
+    InternalError = 8,
+}
+
+thread_local! {
+    /// Message of the last failed call on this thread.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// A failed call: the status returned to C and the message kept for `promptpay_last_error`.
+struct Failure {
+    status: PromptPayStatus,
+    message: String,
+}
+
+impl Failure {
+    fn new(status: PromptPayStatus, message: impl ToString) -> Self {
+        Failure {
+            status,
+            message: message.to_string(),
+        }
+    }
+
+    /// Classify an error from payload generation.
+    fn generation(err: PromptPayError) -> Self {
+        let status = match err {
+            PromptPayError::NegativeAmount
+            | PromptPayError::NonFiniteAmount
+            | PromptPayError::AmountTooLarge
+            | PromptPayError::AmountTooPrecise
+            | PromptPayError::MissingAmount
+            | PromptPayError::InvalidValue {
+                field: Field::Amount,
+                ..
+            } => PromptPayStatus::InvalidAmount,
+            _ => PromptPayStatus::InvalidInput,
+        };
+        Failure::new(status, err)
+    }
+
+    /// Classify an error from payload parsing.
+    fn parsing(err: PromptPayError) -> Self {
+        let status = match err {
+            PromptPayError::CrcMismatch { .. } => PromptPayStatus::CrcMismatch,
+            _ => PromptPayStatus::InvalidPayload,
+        };
+        Failure::new(status, err)
+    }
+}
+
+/// Run the body of a C function, turning failures and panics into a status and keeping
+/// the error message for `promptpay_last_error`.
+fn run(body: impl FnOnce() -> Result<(), Failure>) -> PromptPayStatus {
+    let result = panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|_| {
+        Err(Failure::new(
+            PromptPayStatus::InternalError,
+            "internal error in prompt_pay",
+        ))
+    });
+
+    let (status, message) = match result {
+        Ok(()) => (PromptPayStatus::Ok, None),
+        Err(failure) => {
+            // Messages come from Rust strings, which may hold NUL but never in practice
+            let message = failure.message.replace('\0', "");
+            (failure.status, CString::new(message).ok())
+        }
+    };
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+    status
+}
+
+/// Borrow a required C string argument.
+///
+/// # Safety
+/// `value` must be null or point to a NUL-terminated string that outlives the call.
+unsafe fn required_str<'a>(value: *const c_char, name: &str) -> Result<&'a str, Failure> {
+    if value.is_null() {
+        return Err(Failure::new(
+            PromptPayStatus::NullPointer,
+            format!("{} is null", name),
+        ));
+    }
+    optional_str(value, name).map(Option::unwrap_or_default)
+}
+
+/// Borrow an optional C string argument, where null means absent.
+///
+/// # Safety
+/// `value` must be null or point to a NUL-terminated string that outlives the call.
+unsafe fn optional_str<'a>(value: *const c_char, name: &str) -> Result<Option<&'a str>, Failure> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(value).to_str().map(Some).map_err(|_| {
+        Failure::new(
+            PromptPayStatus::InvalidUtf8,
+            format!("{} is not valid UTF-8", name),
+        )
+    })
+}
+
+/// Hand a string to the caller through an out pointer, to be freed with
+/// `promptpay_free_string`.
+///
+/// # Safety
+/// `out` must be null or valid for writes.
+unsafe fn write_string(out: *mut *mut c_char, value: String) -> Result<(), Failure> {
+    if out.is_null() {
+        return Err(Failure::new(PromptPayStatus::NullPointer, "out is null"));
+    }
+    let value =
+        CString::new(value).map_err(|err| Failure::new(PromptPayStatus::InternalError, err))?;
+    *out = value.into_raw();
+    Ok(())
+}
+
+/// Generate a static payload without an amount, or a dynamic one with `amount`.
+fn generate(input: InputType, amount: Option<&str>) -> Result<String, Failure> {
+    let mode = match amount {
+        Some(amount) => QrMode::Dynamic(amount.parse::<Amount>().map_err(Failure::generation)?),
+        None => QrMode::Static,
+    };
+    Utils::generate_payload_with_mode(input, mode).map_err(Failure::generation)
+}
+
+/// Generate a PromptPay credit transfer payload to a mobile number, national ID or e-wallet.
+///
+/// `input_type` is one of the `PROMPTPAY_INPUT_*` constants and `value` the number or ID in
+/// any common format, e.g. "081-234-5678". `amount` is a decimal string in Baht such as
+/// "123.45", or null for a static QR code where the payer enters the amount.
+///
+/// On success `*out_payload` receives the payload string, to be freed with
+/// `promptpay_free_string`.
+///
+/// # Safety
+/// `value` and `amount` must be null or NUL-terminated strings, and `out_payload` must be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn promptpay_generate(
+    input_type: u32,
+    value: *const c_char,
+    amount: *const c_char,
+    out_payload: *mut *mut c_char,
+) -> PromptPayStatus {
+    run(|| {
+        let value = required_str(value, "value")?.to_string();
+        let input = match input_type {
+            PROMPTPAY_INPUT_PHONE_NUMBER => InputType::PhoneNumber(value),
+            PROMPTPAY_INPUT_NATIONAL_ID => InputType::NationalID(value),
+            PROMPTPAY_INPUT_EWALLET_ID => InputType::EWalletId(value),
+            other => {
+                return Err(Failure::new(
+                    PromptPayStatus::UnknownInputType,
+                    format!("unknown input type {}", other),
+                ))
+            }
+        };
+        let payload = generate(input, optional_str(amount, "amount")?)?;
+        write_string(out_payload, payload)
+    })
+}
+
+/// Generate a PromptPay credit transfer payload to a bank account.
+///
+/// `bank_code` is the 3-digit bank code, e.g. "004", and `amount` is as for
+/// `promptpay_generate`.
+///
+/// # Safety
+/// The string arguments must be null or NUL-terminated strings, and `out_payload` must be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn promptpay_generate_bank_account(
+    bank_code: *const c_char,
+    account_number: *const c_char,
+    amount: *const c_char,
+    out_payload: *mut *mut c_char,
+) -> PromptPayStatus {
+    run(|| {
+        let input = InputType::BankAccount {
+            bank_code: required_str(bank_code, "bank_code")?.to_string(),
+            account_number: required_str(account_number, "account_number")?.to_string(),
+        };
+        let payload = generate(input, optional_str(amount, "amount")?)?;
+        write_string(out_payload, payload)
+    })
+}
+
+/// Generate a Thai QR bill payment payload.
+///
+/// `reference2` may be null. `amount` is as for `promptpay_generate`.
+///
+/// # Safety
+/// The string arguments must be null or NUL-terminated strings, and `out_payload` must be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn promptpay_generate_bill_payment(
+    biller_id: *const c_char,
+    reference1: *const c_char,
+    reference2: *const c_char,
+    amount: *const c_char,
+    out_payload: *mut *mut c_char,
+) -> PromptPayStatus {
+    run(|| {
+        let input = InputType::BillPayment {
+            biller_id: required_str(biller_id, "biller_id")?.to_string(),
+            reference1: required_str(reference1, "reference1")?.to_string(),
+            reference2: optional_str(reference2, "reference2")?.map(str::to_string),
+        };
+        let payload = generate(input, optional_str(amount, "amount")?)?;
+        write_string(out_payload, payload)
+    })
+}
+
+/// Parse a payload string, verifying its CRC.
+///
+/// On success `*out_json` receives the payload fields as a JSON object, in the same form as
+/// the `serde` feature, to be freed with `promptpay_free_string`.
+///
+/// # Safety
+/// `payload` must be null or a NUL-terminated string, and `out_json` must be null or valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn promptpay_parse(
+    payload: *const c_char,
+    out_json: *mut *mut c_char,
+) -> PromptPayStatus {
+    run(|| {
+        let payload =
+            Utils::parse_payload(required_str(payload, "payload")?).map_err(Failure::parsing)?;
+        let json = serde_json::to_string(&payload)
+            .map_err(|err| Failure::new(PromptPayStatus::InternalError, err))?;
+        write_string(out_json, json)
+    })
+}
+
+/// Calculate the CRC-16 of `data` as 4 uppercase hexadecimal digits, as
+/// `Utils::calculate_precise_crc` does for the CRC data object that ends every payload.
+///
+/// On success `*out_crc` receives the CRC, to be freed with `promptpay_free_string`.
+///
+/// # Safety
+/// `data` must be null or a NUL-terminated string, and `out_crc` must be null or valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn promptpay_calculate_crc(
+    data: *const c_char,
+    out_crc: *mut *mut c_char,
+) -> PromptPayStatus {
+    run(|| {
+        let crc = Utils::calculate_precise_crc(required_str(data, "data")?);
+        write_string(out_crc, crc)
+    })
+}
+
+/// Free a string returned by this library. Null is ignored.
+///
+/// # Safety
+/// `value` must be null or a string returned by this library that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn promptpay_free_string(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+/// The message of the last failed call on the calling thread, or null if the last call
+/// succeeded. The string belongs to the library and stays valid until the next call on the
+/// same thread.
+#[no_mangle]
+pub extern "C" fn promptpay_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::promptpay_utils::{InputType, Utils};
+
+    /// Take ownership of a string returned through an out pointer.
+    unsafe fn take(value: *mut c_char) -> String {
+        let string = CStr::from_ptr(value).to_str().unwrap().to_string();
+        promptpay_free_string(value);
+        string
+    }
+
+    unsafe fn last_error() -> String {
+        CStr::from_ptr(promptpay_last_error())
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_ffi_generate() {
+        let value = CString::new("081-234-5678").unwrap();
+        let amount = CString::new("123.45").unwrap();
+        let mut payload = ptr::null_mut();
+        unsafe {
+            let status = promptpay_generate(
+                PROMPTPAY_INPUT_PHONE_NUMBER,
+                value.as_ptr(),
+                amount.as_ptr(),
+                &mut payload,
+            );
+            assert_eq!(status, PromptPayStatus::Ok);
+            assert!(promptpay_last_error().is_null());
+            let expected =
+                Utils::generate_payload(InputType::PhoneNumber("0812345678".to_string()), 123.45);
+            assert_eq!(take(payload), expected.unwrap());
+
+            let status = promptpay_generate(
+                PROMPTPAY_INPUT_PHONE_NUMBER,
+                value.as_ptr(),
+                ptr::null(),
+                &mut payload,
+            );
+            assert_eq!(status, PromptPayStatus::Ok);
+            assert!(take(payload).starts_with("000201010211"));
+        }
+    }
+
+    #[test]
+    fn test_ffi_generate_errors() {
+        let id = CString::new("1234567890120").unwrap();
+        let amount = CString::new("-1").unwrap();
+        let mut payload = ptr::null_mut();
+        unsafe {
+            let status = promptpay_generate(
+                PROMPTPAY_INPUT_NATIONAL_ID,
+                id.as_ptr(),
+                ptr::null(),
+                &mut payload,
+            );
+            assert_eq!(status, PromptPayStatus::InvalidInput);
+            let expected = Utils::sanitize_national_id("1234567890120".to_string()).unwrap_err();
+            assert_eq!(last_error(), expected.to_string());
+
+            let id = CString::new("1234567890121").unwrap();
+            let status = promptpay_generate(
+                PROMPTPAY_INPUT_NATIONAL_ID,
+                id.as_ptr(),
+                amount.as_ptr(),
+                &mut payload,
+            );
+            assert_eq!(status, PromptPayStatus::InvalidAmount);
+
+            let status = promptpay_generate(7, id.as_ptr(), ptr::null(), &mut payload);
+            assert_eq!(status, PromptPayStatus::UnknownInputType);
+
+            let status = promptpay_generate(
+                PROMPTPAY_INPUT_NATIONAL_ID,
+                ptr::null(),
+                ptr::null(),
+                &mut payload,
+            );
+            assert_eq!(status, PromptPayStatus::NullPointer);
+            assert_eq!(last_error(), "value is null");
+        }
+    }
+
+    #[test]
+    fn test_ffi_parse() {
+        let payload =
+            Utils::generate_payload(InputType::NationalID("1234567890121".to_string()), 10.0)
+                .unwrap();
+        let mut json = ptr::null_mut();
+        unsafe {
+            let input = CString::new(payload.as_str()).unwrap();
+            assert_eq!(
+                promptpay_parse(input.as_ptr(), &mut json),
+                PromptPayStatus::Ok
+            );
+            let fields: serde_json::Value = serde_json::from_str(&take(json)).unwrap();
+            assert_eq!(fields["proxy"]["type"], "national_id");
+            assert_eq!(fields["amount"], "10.00");
+
+            let tampered = CString::new(payload.replace("10.00", "90.00")).unwrap();
+            assert_eq!(
+                promptpay_parse(tampered.as_ptr(), &mut json),
+                PromptPayStatus::CrcMismatch
+            );
+
+            let mut crc = ptr::null_mut();
+            let data = CString::new("123456789").unwrap();
+            assert_eq!(
+                promptpay_calculate_crc(data.as_ptr(), &mut crc),
+                PromptPayStatus::Ok
+            );
+            assert_eq!(take(crc), Utils::calculate_precise_crc("123456789"));
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::emv::{self, DataObject};
+use crate::error::{Field, PromptPayError};
+
+/// Sub-tags of the Merchant Information—Language Template.
+const LANGUAGE_PREFERENCE: &str = "00";
+const MERCHANT_NAME: &str = "01";
+const MERCHANT_CITY: &str = "02";
+
+/// Merchant Information—Language Template (tag 64), the payee name and city in a second
+/// language such as Thai.
+///
+/// Unlike the rest of the payload, these fields may hold any script. Their lengths count
+/// characters (Unicode code points), so a Thai name of up to 25 characters fits even though
+/// it takes three bytes per character.
+///
+/// ```
+/// use prompt_pay::language::MerchantInformationLanguage;
+///
+/// let language = MerchantInformationLanguage::new("th", "ร้านส้มตำป้าแดง").merchant_city("กรุงเทพฯ");
+/// assert_eq!(language.merchant_city.as_deref(), Some("กรุงเทพฯ"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MerchantInformationLanguage {
+    /// ISO 639-1 code of the language, e.g. "th" (sub-tag 00).
+    pub language_preference: String,
+    /// Merchant name in that language, up to 25 characters (sub-tag 01).
+    pub merchant_name: String,
+    /// Merchant city in that language, up to 15 characters (sub-tag 02).
+    pub merchant_city: Option<String>,
+}
+
+impl MerchantInformationLanguage {
+    /// Create a language template with the merchant name in the given language.
+    pub fn new(language_preference: impl Into<String>, merchant_name: impl Into<String>) -> Self {
+        MerchantInformationLanguage {
+            language_preference: language_preference.into(),
+            merchant_name: merchant_name.into(),
+            merchant_city: None,
+        }
+    }
+
+    /// Set the merchant city in the same language (sub-tag 02).
+    pub fn merchant_city(mut self, value: impl Into<String>) -> Self {
+        self.merchant_city = Some(value.into());
+        self
+    }
+
+    /// Build the tag 64 template, checking the language code and the field lengths.
+    ///
+    /// # Returns
+    /// The template, or an error naming the invalid field.
+    pub(crate) fn to_data_object(&self) -> Result<DataObject, PromptPayError> {
+        let language = &self.language_preference;
+        if language.len() != 2 || !language.chars().all(|c| c.is_ascii_lowercase()) {
+            return Err(PromptPayError::InvalidValue {
+                field: Field::LanguagePreference,
+                value: language.clone(),
+            });
+        }
+        emv::check_unicode_text(Field::AlternateMerchantName, &self.merchant_name, 25)?;
+
+        let mut children = vec![
+            DataObject::primitive(LANGUAGE_PREFERENCE, language.as_str()),
+            DataObject::primitive(MERCHANT_NAME, self.merchant_name.as_str()),
+        ];
+        if let Some(city) = &self.merchant_city {
+            emv::check_unicode_text(Field::AlternateMerchantCity, city, 15)?;
+            children.push(DataObject::primitive(MERCHANT_CITY, city.as_str()));
+        }
+
+        Ok(DataObject::template(
+            emv::MERCHANT_INFORMATION_LANGUAGE_TEMPLATE,
+            children,
+        ))
+    }
+
+    /// Read the fields of a decoded tag 64 template, ignoring unknown sub-tags.
+    ///
+    /// # Returns
+    /// The template, or an error if the language preference or merchant name is missing.
+    pub(crate) fn from_data_objects(children: &[DataObject]) -> Result<Self, PromptPayError> {
+        let value = |tag| {
+            emv::find(children, tag)
+                .and_then(DataObject::as_str)
+                .map(str::to_string)
+        };
+
+        Ok(MerchantInformationLanguage {
+            language_preference: value(LANGUAGE_PREFERENCE)
+                .ok_or(PromptPayError::MissingField(Field::LanguagePreference))?,
+            merchant_name: value(MERCHANT_NAME)
+                .ok_or(PromptPayError::MissingField(Field::AlternateMerchantName))?,
+            merchant_city: value(MERCHANT_CITY),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MerchantInformationLanguage;
+    use crate::emv;
+    use crate::error::{Field, PromptPayError};
+
+    #[test]
+    fn test_language_template_encoding() {
+        let language = MerchantInformationLanguage::new("th", "ส้มตำ").merchant_city("ขอนแก่น");
+        let object = language.to_data_object().unwrap();
+        assert_eq!(object.encode().unwrap(), "64260002th0105ส้มตำ0207ขอนแก่น");
+
+        let children = emv::decode("0002th0105ส้มตำ0207ขอนแก่น").unwrap();
+        assert_eq!(
+            MerchantInformationLanguage::from_data_objects(&children).unwrap(),
+            language
+        );
+    }
+
+    #[test]
+    fn test_language_template_validation() {
+        let language = MerchantInformationLanguage::new("THA", "ส้มตำ");
+        assert_eq!(
+            language.to_data_object().unwrap_err(),
+            PromptPayError::InvalidValue {
+                field: Field::LanguagePreference,
+                value: "THA".to_string()
+            }
+        );
+
+        // Lengths count characters, so 25 Thai characters (75 bytes) fit but 26 do not
+        let language = MerchantInformationLanguage::new("th", "ก".repeat(26));
+        assert_eq!(
+            language.to_data_object().unwrap_err(),
+            PromptPayError::LengthOutOfRange {
+                field: Field::AlternateMerchantName,
+                min: 1,
+                max: 25,
+                actual: 26
+            }
+        );
+        let language = MerchantInformationLanguage::new("th", "ก".repeat(25));
+        assert!(language.to_data_object().is_ok());
+    }
+}
@@ -1,53 +1,262 @@
+/// EMVCo TLV Module
+///
+/// Merchant-presented QR payloads are a flat sequence of data objects, each written as a
+/// two-digit tag, a two-digit length and the value. Some tags (merchant account information,
+/// additional data, ...) carry a nested sequence of data objects as their value. This module
+/// encodes and decodes that format so payloads never have to be assembled by hand.
+pub mod emv;
+
+/// Payload Model Module
+///
+/// This module holds the structured form of a PromptPay payload: the point of initiation, the
+/// proxy (phone number, national ID, e-wallet, bank account or bill payment) and the amount, along with the
+/// encoder that turns it into a payload string and the parser that turns a string back into it.
+pub mod payload;
+
+/// Additional Data Module
+///
+/// This module provides a builder for the Additional Data Field Template (tag 62), which carries
+/// the bill number, store, terminal and other labels used to reconcile payments.
+pub mod additional_data;
+
+/// Currency Module
+///
+/// This module provides [`currency::Currency`], backed by the ISO 4217 table of numeric codes,
+/// alphabetic codes and minor-unit digits, so amounts are written with the right number of
+/// decimals for the transaction currency.
+pub mod currency;
+
+/// Language Module
+///
+/// This module provides the Merchant Information—Language Template (tag 64), which carries the
+/// merchant name and city in a second language such as Thai for the payer's banking app.
+pub mod language;
+
+/// Amount Module
+///
+/// This module provides [`amount::Amount`], an exact amount of money stored as whole minor
+/// units of the payload currency, so amounts never pass through floating point on their way
+/// into a payload.
+pub mod amount;
+
+/// Error Module
+///
+/// This module defines [`error::PromptPayError`], the error returned by every fallible
+/// function in the crate, along with the [`error::Field`] it refers to, so callers can match on
+/// the failure instead of comparing message strings.
+pub mod error;
+
+/// Tax ID Module
+///
+/// This module validates the mod-11 check digit of Thai national IDs and juristic-person
+/// tax IDs, and tells the two apart, so mistyped IDs are caught before they reach a QR code.
+pub mod tax_id;
+
+/// Builder Module
+///
+/// This module provides [`builder::PromptPayBuilder`], which assembles a payload from a
+/// mandatory proxy and optional chained fields instead of a growing list of positional
+/// arguments.
+pub mod builder;
+
+/// Render Module
+///
+/// This module turns payload strings into QR codes. Each output format sits behind its own
+/// cargo feature: `svg` for SVG documents, `png` for PNG images, bare or inside a Thai QR
+/// Payment frame, and `terminal` for Unicode text to print in a terminal.
+#[cfg(any(feature = "svg", feature = "png", feature = "terminal"))]
+pub mod render;
+
+/// WebAssembly Module
+///
+/// This module exports payload generation, parsing and SVG rendering to JavaScript through
+/// wasm-bindgen when the `wasm` feature is enabled, so browsers and Node run the same code as
+/// Rust callers. The generated TypeScript definitions describe the JSON shape of inputs and
+/// payloads used by the `serde` feature.
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// C ABI Module
+///
+/// This module exposes payload generation, parsing and CRC calculation as C functions when
+/// the `ffi` feature is enabled, for C firmware and cgo callers. The matching header is
+/// `include/promptpay.h`, generated with cbindgen from this module.
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 /// PromptPay Module
 ///
-/// This module provides utilities to generate PromptPay payloads for Thailand's PromptPay system.
+/// This module provides utilities to generate and parse PromptPay payloads for Thailand's PromptPay system.
 /// It ensures compliance with the required format, including phone number/national ID sanitization,
 /// amount formatting, and CRC checksum calculation.
-
 pub mod promptpay_utils {
     // Import the crc crate for CRC checksum calculation
     use crc::{Algorithm, Crc};
 
+    use crate::emv::DataObject;
+    use crate::error::check_digits;
+    use crate::payload::{BANK_ACCOUNT, BILLER_ID, EWALLET_ID, NATIONAL_ID, PHONE_NUMBER};
+    use crate::tax_id;
+
+    use std::convert::TryFrom;
+
+    pub use crate::additional_data::AdditionalData;
+    pub use crate::amount::{Amount, AmountPadding};
+    pub use crate::builder::PromptPayBuilder;
+    pub use crate::currency::Currency;
+    pub use crate::error::{Field, PromptPayError};
+    pub use crate::language::MerchantInformationLanguage;
+    pub use crate::payload::{
+        Percentage, PointOfInitiation, PromptPayPayload, Proxy, QrMode, TipOrConvenienceFee,
+    };
+    pub use crate::tax_id::TaxIdKind;
+
+    /// Account number lengths of Thai banks, keyed by their 3-digit bank code.
+    const BANK_ACCOUNT_LENGTHS: &[(&str, usize)] = &[
+        ("002", 10), // Bangkok Bank
+        ("004", 10), // Kasikornbank
+        ("006", 10), // Krungthai Bank
+        ("011", 10), // TMBThanachart Bank
+        ("014", 10), // Siam Commercial Bank
+        ("017", 10), // Citibank
+        ("020", 10), // Standard Chartered Bank (Thai)
+        ("022", 10), // CIMB Thai Bank
+        ("024", 10), // United Overseas Bank (Thai)
+        ("025", 10), // Bank of Ayudhya (Krungsri)
+        ("030", 12), // Government Savings Bank
+        ("033", 12), // Government Housing Bank
+        ("034", 12), // Bank for Agriculture and Agricultural Cooperatives
+        ("066", 10), // Islamic Bank of Thailand
+        ("067", 10), // Tisco Bank
+        ("069", 10), // Kiatnakin Phatra Bank
+        ("070", 10), // ICBC (Thai)
+        ("071", 10), // Thai Credit Bank
+        ("073", 10), // Land and Houses Bank
+    ];
+
     /// A utility struct for generating PromptPay payloads.
     pub struct Utils;
 
-    /// Enum for specifying the input type, either a phone number or national ID.
+    /// Enum for specifying the input type: a PromptPay credit transfer proxy (phone number,
+    /// national ID, e-wallet ID or bank account) or a Thai QR bill payment.
+    ///
+    /// With the `serde` feature an input serializes as its type and value, e.g.
+    /// `{"type":"phone","value":"0812345678"}` or
+    /// `{"type":"bank_account","value":{"bank_code":"004","account_number":"1234567890"}}`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(tag = "type", content = "value", rename_all = "snake_case")
+    )]
     pub enum InputType {
+        #[cfg_attr(feature = "serde", serde(rename = "phone"))]
         PhoneNumber(String),
+        #[cfg_attr(feature = "serde", serde(rename = "national_id"))]
         NationalID(String),
+        #[cfg_attr(feature = "serde", serde(rename = "ewallet_id"))]
+        EWalletId(String),
+        BankAccount {
+            bank_code: String,
+            account_number: String,
+        },
+        /// Bill payment to a 15-digit biller ID (tax ID plus 2-digit suffix), with the
+        /// required reference 1 and optional reference 2 passed back to the biller.
+        BillPayment {
+            biller_id: String,
+            reference1: String,
+            reference2: Option<String>,
+        },
     }
 
     impl Utils {
-        /// Generate the PromptPay payload string based on the input type (phone number or national ID) and amount.
+        /// Generate the PromptPay payload string based on the input type and amount.
+        ///
+        /// The payload is a dynamic (one-time) QR code; use [`Utils::generate_payload_with_mode`]
+        /// for a reusable QR code without an amount.
         ///
         /// # Parameters
-        /// - `input`: The input can either be a phone number or a national ID.
-        /// - `amount`: The amount to be transferred in Baht.
+        /// - `input`: The PromptPay proxy or bill payment to be paid.
+        /// - `amount`: The amount to be transferred in Baht, rounded to the nearest satang.
         ///
         /// # Returns
-        /// A formatted PromptPay payload as a string, or an error if the input is invalid.
-        pub fn generate_payload(input: InputType, amount: f64) -> Result<String, String> {
-            // Sanitize the input (phone number or national ID)
-            let formatted_id = match input {
-                InputType::PhoneNumber(phone) => Self::sanitize_phone_number(phone)?,
-                InputType::NationalID(id) => Self::sanitize_national_id(id)?,
-            };
-
-            // Convert the amount to satangs (1 Baht = 100 satangs) and format it
-            let amount_satangs = (amount * 100.0).round() / 100.0;
-            let formatted_amount = format!("{:09.2}", amount_satangs);
+        /// A formatted PromptPay payload as a string, or an error if the input is invalid or the
+        /// amount is negative, not finite or too large.
+        pub fn generate_payload(input: InputType, amount: f64) -> Result<String, PromptPayError> {
+            let amount = Amount::try_from(amount)?;
+            Self::generate_payload_with_mode(input, QrMode::Dynamic(amount))
+        }
 
-            // Create the PromptPay payload structure
-            let payload = format!(
-                "00020101021129370016A000000677010111{}5802TH53037645409{}6304",
-                formatted_id, formatted_amount
-            );
+        /// Generate a static (reusable) or dynamic (one-time) PromptPay payload string.
+        ///
+        /// # Parameters
+        /// - `input`: The PromptPay proxy or bill payment to be paid.
+        /// - `mode`: [`QrMode::Static`] to let the payer enter the amount, or
+        ///   [`QrMode::Dynamic`] with the amount to be transferred.
+        ///
+        /// # Returns
+        /// A formatted PromptPay payload as a string, or an error if the input is invalid.
+        pub fn generate_payload_with_mode(
+            input: InputType,
+            mode: QrMode,
+        ) -> Result<String, PromptPayError> {
+            PromptPayBuilder::new(input).mode(mode).build()?.encode()
+        }
 
-            // Calculate the CRC checksum for the payload
-            let crc = Self::calculate_precise_crc(&payload);
-            let final_payload = format!("{}{}", payload, crc);
+        /// Sanitize the input into the proxy a payload pays into.
+        ///
+        /// Use this together with [`PromptPayPayload::new`] to set fields such as
+        /// [`AdditionalData`] before encoding the payload, or use [`PromptPayBuilder`],
+        /// which sanitizes the input itself.
+        ///
+        /// # Parameters
+        /// - `input`: The PromptPay proxy or bill payment to be paid.
+        ///
+        /// # Returns
+        /// The sanitized proxy, or an error if the input is invalid.
+        pub fn sanitize_input(input: InputType) -> Result<Proxy, PromptPayError> {
+            Ok(match input {
+                InputType::PhoneNumber(phone) => {
+                    Proxy::PhoneNumber(Self::normalize_phone_number(phone)?)
+                }
+                InputType::NationalID(id) => Proxy::NationalID(Self::normalize_national_id(id)?),
+                InputType::EWalletId(id) => Proxy::EWalletId(Self::normalize_ewallet_id(id)?),
+                InputType::BankAccount {
+                    bank_code,
+                    account_number,
+                } => {
+                    let (bank_code, account_number) =
+                        Self::normalize_bank_account(bank_code, account_number)?;
+                    Proxy::BankAccount {
+                        bank_code,
+                        account_number,
+                    }
+                }
+                InputType::BillPayment {
+                    biller_id,
+                    reference1,
+                    reference2,
+                } => Proxy::BillPayment {
+                    biller_id: Self::normalize_biller_id(biller_id)?,
+                    reference1: Self::normalize_bill_reference(reference1, Field::Reference1)?,
+                    reference2: reference2
+                        .map(|reference| {
+                            Self::normalize_bill_reference(reference, Field::Reference2)
+                        })
+                        .transpose()?,
+                },
+            })
+        }
 
-            Ok(final_payload)
+        /// Parse a PromptPay payload string back into its fields.
+        ///
+        /// # Parameters
+        /// - `payload`: The payload string, as read from a QR code.
+        ///
+        /// # Returns
+        /// The decoded payload, or an error if the CRC does not match or the payload is malformed.
+        pub fn parse_payload(payload: &str) -> Result<PromptPayPayload, PromptPayError> {
+            PromptPayPayload::parse(payload)
         }
 
         /// Sanitize and format the phone number to meet PromptPay's requirements.
@@ -57,22 +266,66 @@ pub mod promptpay_utils {
         ///
         /// # Returns
         /// A sanitized phone number, or an error if the format is invalid.
-        pub fn sanitize_phone_number(phone_number: String) -> Result<String, String> {
-            let sanitized = phone_number
-                .trim()
-                .replace(['-', '+'], "")
-                .replace("66", "")
-                .trim_start_matches('0')
-                .to_string();
-
-            if sanitized.len() != 9 {
-                return Err("Invalid phone number format".to_string());
+        pub fn sanitize_phone_number(phone_number: String) -> Result<String, PromptPayError> {
+            DataObject::primitive(PHONE_NUMBER, Self::normalize_phone_number(phone_number)?)
+                .encode()
+        }
+
+        /// Parse a Thai mobile number and return the PromptPay proxy value (`0066` followed by
+        /// the 9-digit subscriber number).
+        ///
+        /// Accepts the domestic form ("081-234-5678") as well as the international forms with
+        /// "+66", "0066" or "66", and ignores spaces, dashes, dots and parentheses.
+        fn normalize_phone_number(phone_number: String) -> Result<String, PromptPayError> {
+            let trimmed = phone_number.trim();
+            let (international, rest) = match trimmed.strip_prefix('+') {
+                Some(rest) => (true, rest),
+                None => (false, trimmed),
+            };
+
+            let mut digits = String::new();
+            for c in rest.chars() {
+                match c {
+                    '0'..='9' => digits.push(c),
+                    '-' | '.' | '(' | ')' => {}
+                    c if c.is_whitespace() => {}
+                    character => {
+                        return Err(PromptPayError::InvalidCharacter {
+                            field: Field::PhoneNumber,
+                            character,
+                        })
+                    }
+                }
             }
 
-            // Format the phone number with the country code "01130066"
-            let formatted_phone_number = format!("01130066{}", sanitized);
+            // Strip the country code or trunk prefix to get the subscriber number
+            let subscriber = if international {
+                digits
+                    .strip_prefix("66")
+                    .ok_or_else(|| PromptPayError::InvalidValue {
+                        field: Field::PhoneNumber,
+                        value: trimmed.to_string(),
+                    })?
+            } else if let Some(subscriber) = digits.strip_prefix("0066") {
+                subscriber
+            } else if digits.len() == 11 && digits.starts_with("66") {
+                &digits[2..]
+            } else {
+                digits.as_str()
+            };
+            // "+66 (0)81..." keeps the trunk prefix after the country code
+            let subscriber = match subscriber.strip_prefix('0') {
+                Some(stripped) if subscriber.len() == 10 => stripped,
+                _ => subscriber,
+            };
+
+            if !subscriber.is_empty() && !subscriber.starts_with(['6', '8', '9']) {
+                return Err(PromptPayError::NotMobileNumber(trimmed.to_string()));
+            }
+            check_digits(Field::PhoneNumber, subscriber, 9)?;
 
-            Ok(formatted_phone_number)
+            // Prefix the phone number with the country code "0066"
+            Ok(format!("0066{}", subscriber))
         }
 
         /// Sanitize and format the national ID to meet PromptPay's requirements.
@@ -81,16 +334,116 @@ pub mod promptpay_utils {
         /// - `national_id`: The national ID string to be sanitized.
         ///
         /// # Returns
-        /// A sanitized national ID, or an error if the format is invalid.
-        pub fn sanitize_national_id(national_id: String) -> Result<String, String> {
+        /// A sanitized national ID, or an error if the format or check digit is invalid.
+        pub fn sanitize_national_id(national_id: String) -> Result<String, PromptPayError> {
+            DataObject::primitive(NATIONAL_ID, Self::normalize_national_id(national_id)?).encode()
+        }
+
+        /// Strip the formatting from a national ID and return the 13-digit PromptPay proxy value.
+        fn normalize_national_id(national_id: String) -> Result<String, PromptPayError> {
             let sanitized = national_id.trim().replace('-', "");
 
-            if sanitized.len() != 13 || !sanitized.chars().all(char::is_numeric) {
-                return Err("Invalid national ID format".to_string());
+            tax_id::check(Field::NationalID, &sanitized)?;
+            Ok(sanitized)
+        }
+
+        /// Sanitize and format the e-wallet ID to meet PromptPay's requirements.
+        ///
+        /// # Parameters
+        /// - `ewallet_id`: The e-wallet ID string to be sanitized.
+        ///
+        /// # Returns
+        /// A sanitized e-wallet ID, or an error if the format is invalid.
+        pub fn sanitize_ewallet_id(ewallet_id: String) -> Result<String, PromptPayError> {
+            DataObject::primitive(EWALLET_ID, Self::normalize_ewallet_id(ewallet_id)?).encode()
+        }
+
+        /// Strip the formatting from an e-wallet ID and return the 15-digit PromptPay proxy value.
+        fn normalize_ewallet_id(ewallet_id: String) -> Result<String, PromptPayError> {
+            let sanitized = ewallet_id.trim().replace(['-', ' '], "");
+
+            check_digits(Field::EWalletId, &sanitized, 15)?;
+            Ok(sanitized)
+        }
+
+        /// Sanitize and format a bank account to meet PromptPay's requirements.
+        ///
+        /// # Parameters
+        /// - `bank_code`: The 3-digit Thai bank code, e.g. "004" for Kasikornbank.
+        /// - `account_number`: The account number string to be sanitized.
+        ///
+        /// # Returns
+        /// A sanitized bank account, or an error if the bank is unknown or the account number
+        /// has the wrong length for that bank.
+        pub fn sanitize_bank_account(
+            bank_code: String,
+            account_number: String,
+        ) -> Result<String, PromptPayError> {
+            let (bank_code, account_number) =
+                Self::normalize_bank_account(bank_code, account_number)?;
+            DataObject::primitive(BANK_ACCOUNT, format!("{}{}", bank_code, account_number)).encode()
+        }
+
+        /// Strip the formatting from a bank account and check the account number length
+        /// against the bank it belongs to.
+        fn normalize_bank_account(
+            bank_code: String,
+            account_number: String,
+        ) -> Result<(String, String), PromptPayError> {
+            let bank_code = bank_code.trim().to_string();
+            let account_number = account_number.trim().replace(['-', ' '], "");
+
+            let expected_length = BANK_ACCOUNT_LENGTHS
+                .iter()
+                .find(|(code, _)| *code == bank_code)
+                .map(|(_, length)| *length)
+                .ok_or_else(|| PromptPayError::UnknownBankCode(bank_code.clone()))?;
+
+            check_digits(Field::BankAccount, &account_number, expected_length)?;
+            Ok((bank_code, account_number))
+        }
+
+        /// Sanitize and format the biller ID of a Thai QR bill payment.
+        ///
+        /// # Parameters
+        /// - `biller_id`: The biller's 13-digit tax ID followed by a 2-digit suffix.
+        ///
+        /// # Returns
+        /// A sanitized biller ID, or an error if the format is invalid or the tax ID it starts
+        /// with has the wrong check digit.
+        pub fn sanitize_biller_id(biller_id: String) -> Result<String, PromptPayError> {
+            DataObject::primitive(BILLER_ID, Self::normalize_biller_id(biller_id)?).encode()
+        }
+
+        /// Strip the formatting from a biller ID and return the 15-digit value.
+        fn normalize_biller_id(biller_id: String) -> Result<String, PromptPayError> {
+            let sanitized = biller_id.trim().replace(['-', ' '], "");
+
+            check_digits(Field::BillerId, &sanitized, 15)?;
+            // The first 13 digits are the biller's tax ID
+            tax_id::check(Field::BillerId, &sanitized[..13])?;
+            Ok(sanitized)
+        }
+
+        /// Check a bill payment reference: up to 20 upper-case letters and digits.
+        fn normalize_bill_reference(
+            reference: String,
+            field: Field,
+        ) -> Result<String, PromptPayError> {
+            let sanitized = reference.trim().to_ascii_uppercase();
+
+            if let Some(character) = sanitized.chars().find(|c| !c.is_ascii_alphanumeric()) {
+                return Err(PromptPayError::InvalidCharacter { field, character });
+            }
+            if sanitized.is_empty() || sanitized.len() > 20 {
+                return Err(PromptPayError::LengthOutOfRange {
+                    field,
+                    min: 1,
+                    max: 20,
+                    actual: sanitized.len(),
+                });
             }
-            // Format the national ID with the prefix "0213"
-            let formatted_national_id = format!("0213{}", sanitized);
-            Ok(formatted_national_id)
+            Ok(sanitized)
         }
 
         /// Calculate the CRC-16 checksum (XMODEM) for a given payload.
@@ -128,7 +481,9 @@ pub mod promptpay_utils {
 
 #[cfg(test)]
 mod tests {
-    use super::promptpay_utils::{InputType, Utils};
+    use super::promptpay_utils::{
+        Field, InputType, PointOfInitiation, PromptPayError, QrMode, Utils,
+    };
 
     #[test]
     fn test_sanitize_phone_number_valid() {
@@ -143,13 +498,70 @@ mod tests {
         let input = "+66-81234".to_string();
         let result = Utils::sanitize_phone_number(input);
         assert!(result.is_err());
-        assert_eq!(result.err().unwrap(), "Invalid phone number format");
+        assert_eq!(
+            result.err().unwrap(),
+            PromptPayError::InvalidLength {
+                field: Field::PhoneNumber,
+                expected: 9,
+                actual: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_sanitize_phone_number_forms() {
+        for input in [
+            "0812345678",
+            "081 234 5678",
+            "081.234.5678",
+            "+66 81 234 5678",
+            "+66 (0) 81-234-5678",
+            "0066812345678",
+            "66812345678",
+        ] {
+            assert_eq!(
+                Utils::sanitize_phone_number(input.to_string()).unwrap(),
+                "01130066812345678",
+                "{}",
+                input
+            );
+        }
+        // Only the country code may be stripped, not every "66" in the number
+        assert_eq!(
+            Utils::sanitize_phone_number("0866-661-234".to_string()).unwrap(),
+            "01130066866661234"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_phone_number_not_mobile() {
+        let result = Utils::sanitize_phone_number("02-123-4567".to_string());
+        assert_eq!(
+            result.err().unwrap(),
+            PromptPayError::NotMobileNumber("02-123-4567".to_string())
+        );
+        let result = Utils::sanitize_phone_number("+1 415 555 0100".to_string());
+        assert!(matches!(
+            result.err().unwrap(),
+            PromptPayError::InvalidValue {
+                field: Field::PhoneNumber,
+                ..
+            }
+        ));
+        let result = Utils::sanitize_phone_number("081-234-567x".to_string());
+        assert_eq!(
+            result.err().unwrap(),
+            PromptPayError::InvalidCharacter {
+                field: Field::PhoneNumber,
+                character: 'x'
+            }
+        );
     }
 
     #[test]
     fn test_sanitize_national_id_valid() {
-        let input = "1234567890123".to_string();
-        let expected = "02131234567890123".to_string();
+        let input = "1234567890121".to_string();
+        let expected = "02131234567890121".to_string();
         let result = Utils::sanitize_national_id(input).unwrap();
         assert_eq!(result, expected);
     }
@@ -159,7 +571,98 @@ mod tests {
         let input = "1234-5678".to_string();
         let result = Utils::sanitize_national_id(input);
         assert!(result.is_err());
-        assert_eq!(result.err().unwrap(), "Invalid national ID format");
+        assert_eq!(
+            result.err().unwrap(),
+            PromptPayError::InvalidLength {
+                field: Field::NationalID,
+                expected: 13,
+                actual: 8
+            }
+        );
+    }
+
+    #[test]
+    fn test_sanitize_national_id_check_digit() {
+        let result = Utils::sanitize_national_id("1-2345-67890-12-3".to_string());
+        assert_eq!(
+            result.err().unwrap(),
+            PromptPayError::InvalidCheckDigit {
+                field: Field::NationalID,
+                expected: '1',
+                actual: '3'
+            }
+        );
+    }
+
+    #[test]
+    fn test_sanitize_ewallet_id_valid() {
+        let input = "140-000001234567".to_string();
+        let expected = "0315140000001234567".to_string();
+        let result = Utils::sanitize_ewallet_id(input).unwrap();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_sanitize_ewallet_id_invalid() {
+        let input = "14000000123456".to_string();
+        let result = Utils::sanitize_ewallet_id(input);
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap(),
+            PromptPayError::InvalidLength {
+                field: Field::EWalletId,
+                expected: 15,
+                actual: 14
+            }
+        );
+    }
+
+    #[test]
+    fn test_sanitize_bank_account_valid() {
+        let result =
+            Utils::sanitize_bank_account("004".to_string(), "123-4-56789-0".to_string()).unwrap();
+        assert_eq!(result, "04130041234567890");
+    }
+
+    #[test]
+    fn test_sanitize_bank_account_invalid() {
+        let result = Utils::sanitize_bank_account("030".to_string(), "1234567890".to_string());
+        assert_eq!(
+            result.err().unwrap(),
+            PromptPayError::InvalidLength {
+                field: Field::BankAccount,
+                expected: 12,
+                actual: 10
+            }
+        );
+        let result = Utils::sanitize_bank_account("999".to_string(), "1234567890".to_string());
+        assert_eq!(
+            result.err().unwrap(),
+            PromptPayError::UnknownBankCode("999".to_string())
+        );
+    }
+
+    #[test]
+    fn test_sanitize_biller_id_valid() {
+        let input = "0107536000315-08".to_string();
+        let expected = "0115010753600031508".to_string();
+        let result = Utils::sanitize_biller_id(input).unwrap();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_sanitize_biller_id_invalid() {
+        let input = "0107536000315".to_string();
+        let result = Utils::sanitize_biller_id(input);
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap(),
+            PromptPayError::InvalidLength {
+                field: Field::BillerId,
+                expected: 15,
+                actual: 13
+            }
+        );
     }
 
     #[test]
@@ -177,11 +680,107 @@ mod tests {
         assert!(result.contains("812345678"));
     }
 
+    #[test]
+    fn test_generate_payload_layout() {
+        let input = InputType::PhoneNumber("0812345678".to_string());
+        let result = Utils::generate_payload(input, 123.45).unwrap();
+        let (payload, crc) = result.split_at(result.len() - 4);
+        assert_eq!(
+            payload,
+            "00020101021229370016A0000006770101110113006681234567853037645406123.455802TH6304"
+        );
+        assert_eq!(crc, Utils::calculate_precise_crc(payload));
+    }
+
+    #[test]
+    fn test_generate_static_payload_omits_amount() {
+        let input = InputType::PhoneNumber("0812345678".to_string());
+        let result = Utils::generate_payload_with_mode(input, QrMode::Static).unwrap();
+        assert!(result.starts_with("000201010211"));
+        let payload = Utils::parse_payload(&result).unwrap();
+        assert_eq!(payload.point_of_initiation, PointOfInitiation::Static);
+        assert!(payload.amount.is_none());
+    }
+
+    #[test]
+    fn test_generate_payload_is_dynamic() {
+        let input = InputType::PhoneNumber("0812345678".to_string());
+        let result = Utils::generate_payload(input, 50.0).unwrap();
+        assert!(result.starts_with("000201010212"));
+        let payload = Utils::parse_payload(&result).unwrap();
+        assert_eq!(payload.point_of_initiation, PointOfInitiation::Dynamic);
+        assert!(payload.amount.is_some());
+    }
+
+    #[test]
+    fn test_generate_payload_ewallet_id() {
+        let input = InputType::EWalletId("140000001234567".to_string());
+        let amount = 123.45;
+        let result = Utils::generate_payload(input, amount).unwrap();
+        assert!(result.contains("0315140000001234567"));
+    }
+
+    #[test]
+    fn test_generate_payload_bill_payment() {
+        let input = InputType::BillPayment {
+            biller_id: "010753600031508".to_string(),
+            reference1: "inv001".to_string(),
+            reference2: None,
+        };
+        let result = Utils::generate_payload(input, 500.0).unwrap();
+        assert!(result.contains("30490016A00000067701011201150107536000315080206INV001"));
+    }
+
+    #[test]
+    fn test_generate_payload_bill_payment_invalid_reference() {
+        let input = InputType::BillPayment {
+            biller_id: "010753600031508".to_string(),
+            reference1: "INV-001".to_string(),
+            reference2: Some("X".repeat(21)),
+        };
+        let result = Utils::generate_payload(input, 500.0);
+        assert_eq!(
+            result.err().unwrap(),
+            PromptPayError::InvalidCharacter {
+                field: Field::Reference1,
+                character: '-'
+            }
+        );
+    }
+
+    #[test]
+    fn test_generate_payload_invalid_amount() {
+        for amount in [f64::NAN, f64::INFINITY, -1.0] {
+            let input = InputType::PhoneNumber("0812345678".to_string());
+            assert!(Utils::generate_payload(input, amount).is_err());
+        }
+    }
+
     #[test]
     fn test_generate_payload_national_id() {
-        let input = InputType::NationalID("1234567890123".to_string());
+        let input = InputType::NationalID("1234567890121".to_string());
         let amount = 123.45;
         let result = Utils::generate_payload(input, amount).unwrap();
-        assert!(result.contains("1234567890123"));
+        assert!(result.contains("1234567890121"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_input_type_serde() {
+        let input = InputType::PhoneNumber("0812345678".to_string());
+        let json = serde_json::to_string(&input).unwrap();
+        assert_eq!(json, r#"{"type":"phone","value":"0812345678"}"#);
+        assert_eq!(serde_json::from_str::<InputType>(&json).unwrap(), input);
+
+        let json =
+            r#"{"type":"bank_account","value":{"bank_code":"004","account_number":"1234567890"}}"#;
+        assert_eq!(
+            serde_json::from_str::<InputType>(json).unwrap(),
+            InputType::BankAccount {
+                bank_code: "004".to_string(),
+                account_number: "1234567890".to_string()
+            }
+        );
+        assert!(serde_json::from_str::<InputType>(r#"{"type":"fax","value":"1"}"#).is_err());
     }
 }
@@ -0,0 +1,1358 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::additional_data::AdditionalData;
+use crate::amount::{Amount, AmountPadding};
+use crate::currency::Currency;
+use crate::emv::{self, DataObject};
+use crate::error::{check_digits, Field, PromptPayError};
+use crate::language::MerchantInformationLanguage;
+use crate::promptpay_utils::{InputType, Utils};
+
+/// Tag of the PromptPay credit transfer merchant account template.
+pub(crate) const CREDIT_TRANSFER: &str = "29";
+/// Tag of the Thai QR bill payment merchant account template.
+pub(crate) const BILL_PAYMENT: &str = "30";
+/// Application ID of the PromptPay credit transfer template.
+pub(crate) const CREDIT_TRANSFER_AID: &str = "A000000677010111";
+/// Application ID of the Thai QR bill payment template.
+pub(crate) const BILL_PAYMENT_AID: &str = "A000000677010112";
+
+/// Sub-tags shared by both merchant account templates.
+pub(crate) const APPLICATION_ID: &str = "00";
+/// Sub-tags of the credit transfer template.
+pub(crate) const PHONE_NUMBER: &str = "01";
+pub(crate) const NATIONAL_ID: &str = "02";
+pub(crate) const EWALLET_ID: &str = "03";
+pub(crate) const BANK_ACCOUNT: &str = "04";
+/// Sub-tags of the bill payment template.
+pub(crate) const BILLER_ID: &str = "01";
+pub(crate) const REFERENCE_1: &str = "02";
+pub(crate) const REFERENCE_2: &str = "03";
+
+/// Whether the QR code may be paid more than once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum PointOfInitiation {
+    /// A reusable QR code ("11"), e.g. a printed counter sticker.
+    Static,
+    /// A one-time QR code ("12") for a single transaction.
+    Dynamic,
+}
+
+impl PointOfInitiation {
+    /// The value of the Point of Initiation Method data object.
+    pub fn code(&self) -> &'static str {
+        match self {
+            PointOfInitiation::Static => "11",
+            PointOfInitiation::Dynamic => "12",
+        }
+    }
+}
+
+/// How a generated QR code is meant to be paid.
+///
+/// A dynamic QR code always carries the amount, so one cannot be built without it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QrMode {
+    /// A reusable QR code where the payer types in the amount.
+    Static,
+    /// A one-time QR code for the given amount.
+    Dynamic(Amount),
+}
+
+/// A convenience fee percentage from 0 to 100, stored exactly as hundredths of a percent.
+///
+/// ```
+/// use prompt_pay::payload::Percentage;
+///
+/// let fee: Percentage = "3.5".parse().unwrap();
+/// assert_eq!(fee.hundredths(), 350);
+/// assert_eq!(fee.to_string(), "3.50");
+/// assert!("100.01".parse::<Percentage>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Percentage(u16);
+
+impl Percentage {
+    /// One hundred percent.
+    pub const MAX: Percentage = Percentage(10_000);
+
+    /// Create a percentage from a whole number of hundredths of a percent, e.g. 350 for 3.5%.
+    ///
+    /// # Returns
+    /// The percentage, or an error if it is more than 100%.
+    pub fn from_hundredths(hundredths: u16) -> Result<Self, PromptPayError> {
+        if hundredths > Self::MAX.0 {
+            return Err(PromptPayError::InvalidValue {
+                field: Field::ConvenienceFeePercentage,
+                value: Percentage(hundredths).to_string(),
+            });
+        }
+        Ok(Percentage(hundredths))
+    }
+
+    /// The percentage in hundredths of a percent.
+    pub fn hundredths(&self) -> u16 {
+        self.0
+    }
+
+    /// Whether a payload can carry the percentage: from 0.01 to 99.99.
+    fn is_encodable(&self) -> bool {
+        (1..Self::MAX.0).contains(&self.0)
+    }
+}
+
+impl fmt::Display for Percentage {
+    /// Format the percentage with exactly two decimals, e.g. "3.50".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&format!("{}.{:02}", self.0 / 100, self.0 % 100))
+    }
+}
+
+impl FromStr for Percentage {
+    type Err = PromptPayError;
+
+    /// Parse a percentage such as "3.5" or "12.25", with at most two decimals.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let invalid = || PromptPayError::InvalidValue {
+            field: Field::ConvenienceFeePercentage,
+            value: input.to_string(),
+        };
+        let (whole, fraction) = input.split_once('.').unwrap_or((input, ""));
+        if whole.is_empty()
+            || whole.len() > 3
+            || fraction.len() > 2
+            || !whole
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let whole: u16 = whole.parse().map_err(|_| invalid())?;
+        let fraction: u16 = format!("{:0<2}", fraction).parse().map_err(|_| invalid())?;
+        Percentage::from_hundredths(whole * 100 + fraction).map_err(|_| invalid())
+    }
+}
+
+impl TryFrom<f64> for Percentage {
+    type Error = PromptPayError;
+
+    /// Convert a percentage, rounding to the nearest hundredth of a percent.
+    ///
+    /// NaN, negative values and values above 100 are rejected.
+    fn try_from(percentage: f64) -> Result<Self, Self::Error> {
+        let hundredths = (percentage * 100.0).round();
+        if !(0.0..=f64::from(Self::MAX.0)).contains(&hundredths) {
+            return Err(PromptPayError::InvalidValue {
+                field: Field::ConvenienceFeePercentage,
+                value: percentage.to_string(),
+            });
+        }
+        Ok(Percentage(hundredths as u16))
+    }
+}
+
+/// Tip or convenience fee the payer's app adds on top of the amount (tags 55 to 57).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TipOrConvenienceFee {
+    /// Prompt the payer to enter a tip ("01").
+    PromptForTip,
+    /// Add a fixed convenience fee ("02", tag 56).
+    FixedFee(Amount),
+    /// Add a convenience fee as a percentage of the amount, from 0.01 to 99.99 ("03", tag 57).
+    PercentageFee(Percentage),
+}
+
+impl TipOrConvenienceFee {
+    /// Build the indicator and its companion fee data object, writing a fixed fee with the
+    /// decimals of `currency`.
+    fn to_data_objects(self, currency: Currency) -> Result<Vec<DataObject>, PromptPayError> {
+        let indicator = |code| DataObject::primitive(emv::TIP_OR_CONVENIENCE_INDICATOR, code);
+
+        match self {
+            TipOrConvenienceFee::PromptForTip => Ok(vec![indicator("01")]),
+            TipOrConvenienceFee::FixedFee(fee) => {
+                if fee.is_zero() {
+                    return Err(PromptPayError::InvalidValue {
+                        field: Field::ConvenienceFee,
+                        value: fee.format_in(currency),
+                    });
+                }
+                Ok(vec![
+                    indicator("02"),
+                    DataObject::primitive(emv::CONVENIENCE_FEE_FIXED, fee.format_in(currency)),
+                ])
+            }
+            TipOrConvenienceFee::PercentageFee(percentage) => {
+                if !percentage.is_encodable() {
+                    return Err(PromptPayError::InvalidValue {
+                        field: Field::ConvenienceFeePercentage,
+                        value: percentage.to_string(),
+                    });
+                }
+                Ok(vec![
+                    indicator("03"),
+                    DataObject::primitive(emv::CONVENIENCE_FEE_PERCENTAGE, percentage.to_string()),
+                ])
+            }
+        }
+    }
+
+    /// Read the indicator from a decoded payload, checking that exactly the companion tag
+    /// it calls for is present.
+    fn from_data_objects(
+        objects: &[DataObject],
+        currency: Currency,
+    ) -> Result<Option<Self>, PromptPayError> {
+        let indicator = find_value(objects, emv::TIP_OR_CONVENIENCE_INDICATOR);
+        let fixed = find_value(objects, emv::CONVENIENCE_FEE_FIXED);
+        let percentage = find_value(objects, emv::CONVENIENCE_FEE_PERCENTAGE);
+
+        let invalid_fee = |field, value: &str| PromptPayError::InvalidValue {
+            field,
+            value: value.to_string(),
+        };
+
+        match (indicator, fixed, percentage) {
+            (None, None, None) => Ok(None),
+            (Some("01"), None, None) => Ok(Some(TipOrConvenienceFee::PromptForTip)),
+            (Some("02"), Some(fee), None) => {
+                let fee = Amount::parse_in(fee, currency)
+                    .map_err(|_| invalid_fee(Field::ConvenienceFee, fee))?;
+                Ok(Some(TipOrConvenienceFee::FixedFee(fee)))
+            }
+            (Some("03"), None, Some(fee)) => {
+                let percentage = fee.parse::<Percentage>()?;
+                if !percentage.is_encodable() {
+                    return Err(invalid_fee(Field::ConvenienceFeePercentage, fee));
+                }
+                Ok(Some(TipOrConvenienceFee::PercentageFee(percentage)))
+            }
+            (Some("01" | "02" | "03"), _, _) | (None, _, _) => {
+                Err(PromptPayError::InconsistentConvenienceFee)
+            }
+            (Some(code), _, _) => Err(invalid_fee(Field::TipOrConvenienceIndicator, code)),
+        }
+    }
+}
+
+/// The PromptPay target a payload pays into, with the values as they appear in the payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+pub enum Proxy {
+    /// Mobile number in the `0066XXXXXXXXX` proxy form.
+    #[cfg_attr(feature = "serde", serde(rename = "phone"))]
+    PhoneNumber(String),
+    /// 13-digit national ID or tax ID.
+    #[cfg_attr(feature = "serde", serde(rename = "national_id"))]
+    NationalID(String),
+    /// 15-digit e-wallet ID.
+    #[cfg_attr(feature = "serde", serde(rename = "ewallet_id"))]
+    EWalletId(String),
+    /// Bank account, identified by the 3-digit bank code and the account number.
+    BankAccount {
+        bank_code: String,
+        account_number: String,
+    },
+    /// Thai QR bill payment with its biller ID and references.
+    BillPayment {
+        biller_id: String,
+        reference1: String,
+        reference2: Option<String>,
+    },
+}
+
+impl Proxy {
+    /// Check that the proxy is valid and written the way [`Utils::sanitize_input`] writes
+    /// it, e.g. a phone number as "0066812345678" and a national ID with a matching check
+    /// digit.
+    ///
+    /// # Returns
+    /// `Ok(())`, or the error sanitizing the proxy gives, or an invalid value error if the
+    /// proxy is valid but not in its sanitized form.
+    pub fn validate(&self) -> Result<(), PromptPayError> {
+        let input = match self.clone() {
+            Proxy::PhoneNumber(phone) => InputType::PhoneNumber(phone),
+            Proxy::NationalID(id) => InputType::NationalID(id),
+            Proxy::EWalletId(id) => InputType::EWalletId(id),
+            Proxy::BankAccount {
+                bank_code,
+                account_number,
+            } => InputType::BankAccount {
+                bank_code,
+                account_number,
+            },
+            Proxy::BillPayment {
+                biller_id,
+                reference1,
+                reference2,
+            } => InputType::BillPayment {
+                biller_id,
+                reference1,
+                reference2,
+            },
+        };
+        let sanitized = Utils::sanitize_input(input)?;
+
+        for ((field, value), (_, expected)) in self.values().into_iter().zip(sanitized.values()) {
+            if value != expected {
+                return Err(PromptPayError::InvalidValue {
+                    field,
+                    value: value.unwrap_or_default().to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The fields of the proxy with their values.
+    fn values(&self) -> Vec<(Field, Option<&str>)> {
+        match self {
+            Proxy::PhoneNumber(phone) => vec![(Field::PhoneNumber, Some(phone))],
+            Proxy::NationalID(id) => vec![(Field::NationalID, Some(id))],
+            Proxy::EWalletId(id) => vec![(Field::EWalletId, Some(id))],
+            Proxy::BankAccount {
+                bank_code,
+                account_number,
+            } => vec![
+                (Field::BankCode, Some(bank_code)),
+                (Field::BankAccount, Some(account_number)),
+            ],
+            Proxy::BillPayment {
+                biller_id,
+                reference1,
+                reference2,
+            } => vec![
+                (Field::BillerId, Some(biller_id)),
+                (Field::Reference1, Some(reference1)),
+                (Field::Reference2, reference2.as_deref()),
+            ],
+        }
+    }
+
+    /// Build the merchant account information template for this proxy.
+    fn to_data_object(&self) -> DataObject {
+        let (tag, aid, fields) = match self {
+            Proxy::PhoneNumber(phone) => (
+                CREDIT_TRANSFER,
+                CREDIT_TRANSFER_AID,
+                vec![DataObject::primitive(PHONE_NUMBER, phone.as_str())],
+            ),
+            Proxy::NationalID(id) => (
+                CREDIT_TRANSFER,
+                CREDIT_TRANSFER_AID,
+                vec![DataObject::primitive(NATIONAL_ID, id.as_str())],
+            ),
+            Proxy::EWalletId(id) => (
+                CREDIT_TRANSFER,
+                CREDIT_TRANSFER_AID,
+                vec![DataObject::primitive(EWALLET_ID, id.as_str())],
+            ),
+            Proxy::BankAccount {
+                bank_code,
+                account_number,
+            } => (
+                CREDIT_TRANSFER,
+                CREDIT_TRANSFER_AID,
+                vec![DataObject::primitive(
+                    BANK_ACCOUNT,
+                    format!("{}{}", bank_code, account_number),
+                )],
+            ),
+            Proxy::BillPayment {
+                biller_id,
+                reference1,
+                reference2,
+            } => {
+                let mut fields = vec![
+                    DataObject::primitive(BILLER_ID, biller_id.as_str()),
+                    DataObject::primitive(REFERENCE_1, reference1.as_str()),
+                ];
+                if let Some(reference2) = reference2 {
+                    fields.push(DataObject::primitive(REFERENCE_2, reference2.as_str()));
+                }
+                (BILL_PAYMENT, BILL_PAYMENT_AID, fields)
+            }
+        };
+
+        let mut children = vec![DataObject::primitive(APPLICATION_ID, aid)];
+        children.extend(fields);
+        DataObject::template(tag, children)
+    }
+}
+
+/// A PromptPay payload, either decoded from a string or ready to be encoded.
+///
+/// With the `serde` feature the payload serializes to a JSON object with the proxy tagged by
+/// type, e.g. `{"type":"phone","value":"0066812345678"}`, and the amount as a decimal string
+/// in the payload currency.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "PayloadRepr", try_from = "PayloadRepr")
+)]
+pub struct PromptPayPayload {
+    /// Static or dynamic QR code.
+    pub point_of_initiation: PointOfInitiation,
+    /// The account the payment goes to.
+    pub proxy: Proxy,
+    /// The transaction amount, if the payload carries one.
+    pub amount: Option<Amount>,
+    /// Whether the amount is written with leading zeros.
+    pub amount_padding: AmountPadding,
+    /// Tip prompt or convenience fee added to the amount.
+    pub tip_or_convenience_fee: Option<TipOrConvenienceFee>,
+    /// Transaction currency, Thai Baht for PromptPay.
+    pub currency: Currency,
+    /// ISO 3166-1 alpha-2 country code, e.g. "TH".
+    pub country: String,
+    /// Merchant Category Code (ISO 18245), e.g. "5812" for restaurants.
+    pub merchant_category_code: Option<String>,
+    /// Payee name shown by the payer's banking app, up to 25 characters.
+    pub merchant_name: Option<String>,
+    /// Payee city, up to 15 characters.
+    pub merchant_city: Option<String>,
+    /// Payee postal code, up to 10 characters.
+    pub postal_code: Option<String>,
+    /// Bill number, store, terminal and other reconciliation labels (tag 62).
+    pub additional_data: Option<AdditionalData>,
+    /// Merchant name and city in a second language such as Thai (tag 64).
+    pub merchant_information_language: Option<MerchantInformationLanguage>,
+}
+
+impl PromptPayPayload {
+    /// Create a Thai Baht payload paying into `proxy`.
+    ///
+    /// # Parameters
+    /// - `proxy`: The sanitized account the payment goes to.
+    /// - `mode`: Static QR code, or dynamic QR code with its amount.
+    pub fn new(proxy: Proxy, mode: QrMode) -> Self {
+        let (point_of_initiation, amount) = match mode {
+            QrMode::Static => (PointOfInitiation::Static, None),
+            QrMode::Dynamic(amount) => (PointOfInitiation::Dynamic, Some(amount)),
+        };
+
+        PromptPayPayload {
+            point_of_initiation,
+            proxy,
+            amount,
+            amount_padding: AmountPadding::None,
+            tip_or_convenience_fee: None,
+            currency: Currency::THB,
+            country: "TH".to_string(),
+            merchant_category_code: None,
+            merchant_name: None,
+            merchant_city: None,
+            postal_code: None,
+            additional_data: None,
+            merchant_information_language: None,
+        }
+    }
+
+    /// Serialize the payload into the string carried by the QR code, including its CRC.
+    ///
+    /// # Returns
+    /// The payload string, or an error if the proxy is invalid, a dynamic payload has no
+    /// amount or a field does not fit its data object.
+    pub fn encode(&self) -> Result<String, PromptPayError> {
+        self.proxy.validate()?;
+        let mut objects = vec![
+            DataObject::primitive(emv::PAYLOAD_FORMAT_INDICATOR, "01"),
+            DataObject::primitive(emv::POINT_OF_INITIATION, self.point_of_initiation.code()),
+            self.proxy.to_data_object(),
+        ];
+
+        if let Some(mcc) = &self.merchant_category_code {
+            check_digits(Field::MerchantCategoryCode, mcc, 4)?;
+            objects.push(DataObject::primitive(
+                emv::MERCHANT_CATEGORY_CODE,
+                mcc.as_str(),
+            ));
+        }
+
+        objects.push(DataObject::primitive(
+            emv::TRANSACTION_CURRENCY,
+            self.currency.numeric_code(),
+        ));
+
+        match (self.point_of_initiation, self.amount) {
+            (_, Some(amount)) => {
+                let formatted_amount = amount.encode(self.currency, self.amount_padding)?;
+                objects.push(DataObject::primitive(
+                    emv::TRANSACTION_AMOUNT,
+                    formatted_amount,
+                ));
+            }
+            (PointOfInitiation::Dynamic, None) => {
+                return Err(PromptPayError::MissingAmount);
+            }
+            (PointOfInitiation::Static, None) => {}
+        }
+
+        if let Some(fee) = self.tip_or_convenience_fee {
+            objects.extend(fee.to_data_objects(self.currency)?);
+        }
+
+        if self.country.len() != 2 || !self.country.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(PromptPayError::InvalidValue {
+                field: Field::CountryCode,
+                value: self.country.clone(),
+            });
+        }
+        objects.push(DataObject::primitive(
+            emv::COUNTRY_CODE,
+            self.country.as_str(),
+        ));
+
+        let merchant_fields = [
+            (
+                emv::MERCHANT_NAME,
+                Field::MerchantName,
+                &self.merchant_name,
+                25,
+            ),
+            (
+                emv::MERCHANT_CITY,
+                Field::MerchantCity,
+                &self.merchant_city,
+                15,
+            ),
+            (emv::POSTAL_CODE, Field::PostalCode, &self.postal_code, 10),
+        ];
+        for (tag, field, value, max_length) in merchant_fields {
+            if let Some(value) = value {
+                emv::check_text(field, value, max_length)?;
+                objects.push(DataObject::primitive(tag, value.as_str()));
+            }
+        }
+
+        if let Some(additional_data) = &self.additional_data {
+            objects.extend(additional_data.to_data_object()?);
+        }
+
+        if let Some(language) = &self.merchant_information_language {
+            objects.push(language.to_data_object()?);
+        }
+
+        // The CRC covers everything up to and including its own tag and length
+        let payload = format!("{}{}04", emv::encode(&objects)?, emv::CRC);
+        let crc = Utils::calculate_precise_crc(&payload);
+
+        Ok(format!("{}{}", payload, crc))
+    }
+
+    /// Encode the payload and render it as an SVG QR code.
+    ///
+    /// # Returns
+    /// A standalone SVG document, or an error if the payload cannot be encoded.
+    #[cfg(feature = "svg")]
+    pub fn to_svg(
+        &self,
+        options: &crate::render::svg::SvgOptions,
+    ) -> Result<String, PromptPayError> {
+        crate::render::svg::to_svg(&self.encode()?, options)
+    }
+
+    /// Encode the payload and render it as a bare PNG QR code.
+    ///
+    /// # Returns
+    /// The PNG file contents, or an error if the payload cannot be encoded.
+    #[cfg(feature = "png")]
+    pub fn to_png(
+        &self,
+        options: &crate::render::png::PngOptions,
+    ) -> Result<Vec<u8>, PromptPayError> {
+        crate::render::png::to_png(&self.encode()?, options)
+    }
+
+    /// Encode the payload and render it as a PNG QR code inside a Thai QR Payment frame.
+    ///
+    /// Unless `options` sets them, the payee name is taken from the alternate-language
+    /// merchant name or else the merchant name, and the amount from the transaction amount.
+    ///
+    /// # Returns
+    /// The PNG file contents, or an error if the payload cannot be encoded or the text
+    /// cannot be drawn with the given fonts.
+    #[cfg(feature = "png")]
+    pub fn to_framed_png(
+        &self,
+        options: &crate::render::png::FrameOptions,
+    ) -> Result<Vec<u8>, PromptPayError> {
+        let mut options = options.clone();
+        if options.payee_name.is_none() {
+            options.payee_name = self
+                .merchant_information_language
+                .as_ref()
+                .map(|language| language.merchant_name.clone())
+                .or_else(|| self.merchant_name.clone());
+        }
+        if options.amount.is_none() {
+            options.amount = self
+                .amount
+                .map(|amount| format!("{} {}", amount.format_in(self.currency), self.currency));
+        }
+        crate::render::png::to_framed_png(&self.encode()?, &options)
+    }
+
+    /// Encode the payload and render it as a QR code to print in a terminal.
+    ///
+    /// # Returns
+    /// The lines of the code, or an error if the payload cannot be encoded.
+    #[cfg(feature = "terminal")]
+    pub fn to_terminal(
+        &self,
+        options: &crate::render::terminal::TerminalOptions,
+    ) -> Result<String, PromptPayError> {
+        crate::render::terminal::to_terminal(&self.encode()?, options)
+    }
+
+    /// Parse a PromptPay payload string, verifying its CRC.
+    ///
+    /// # Parameters
+    /// - `payload`: The payload string, as read from a QR code.
+    ///
+    /// # Returns
+    /// The decoded payload, or an error if the CRC does not match or the payload is
+    /// not a well-formed PromptPay payload.
+    pub fn parse(payload: &str) -> Result<Self, PromptPayError> {
+        let payload = payload.trim();
+
+        // The CRC is the last data object and covers everything before its value
+        let crc_start = payload
+            .len()
+            .checked_sub(4)
+            .filter(|&start| payload.is_char_boundary(start))
+            .ok_or(PromptPayError::MissingField(Field::Crc))?;
+        let (data, crc) = payload.split_at(crc_start);
+        if !data.ends_with("6304") {
+            return Err(PromptPayError::MissingField(Field::Crc));
+        }
+        let expected_crc = Utils::calculate_precise_crc(data);
+        if !crc.eq_ignore_ascii_case(&expected_crc) {
+            return Err(PromptPayError::CrcMismatch {
+                expected: expected_crc,
+                actual: crc.to_string(),
+            });
+        }
+
+        let objects = emv::decode(payload)?;
+
+        match objects.first() {
+            Some(object) if object.tag() == emv::PAYLOAD_FORMAT_INDICATOR => {
+                if object.as_str() != Some("01") {
+                    return Err(PromptPayError::InvalidValue {
+                        field: Field::PayloadFormatIndicator,
+                        value: object.as_str().unwrap_or_default().to_string(),
+                    });
+                }
+            }
+            _ => return Err(PromptPayError::MissingField(Field::PayloadFormatIndicator)),
+        }
+
+        let point_of_initiation = match find_value(&objects, emv::POINT_OF_INITIATION) {
+            Some("11") => PointOfInitiation::Static,
+            Some("12") => PointOfInitiation::Dynamic,
+            Some(other) => {
+                return Err(PromptPayError::InvalidValue {
+                    field: Field::PointOfInitiation,
+                    value: other.to_string(),
+                })
+            }
+            None => return Err(PromptPayError::MissingField(Field::PointOfInitiation)),
+        };
+
+        let proxy = if let Some(account) = emv::find(&objects, CREDIT_TRANSFER) {
+            parse_credit_transfer(&account.children()?)?
+        } else if let Some(account) = emv::find(&objects, BILL_PAYMENT) {
+            parse_bill_payment(&account.children()?)?
+        } else {
+            return Err(PromptPayError::MissingField(Field::MerchantAccount));
+        };
+
+        // The currency sets how many decimals the amount and fee are written with
+        let currency = find_value(&objects, emv::TRANSACTION_CURRENCY)
+            .ok_or(PromptPayError::MissingField(Field::Currency))?;
+        let currency =
+            Currency::from_numeric(currency).ok_or_else(|| PromptPayError::InvalidValue {
+                field: Field::Currency,
+                value: currency.to_string(),
+            })?;
+
+        let (amount, amount_padding) = match find_value(&objects, emv::TRANSACTION_AMOUNT) {
+            Some(amount) => {
+                let (amount, padding) = Amount::decode(amount, currency)?;
+                (Some(amount), padding)
+            }
+            None => (None, AmountPadding::None),
+        };
+        let tip_or_convenience_fee = TipOrConvenienceFee::from_data_objects(&objects, currency)?;
+
+        let country = find_value(&objects, emv::COUNTRY_CODE)
+            .ok_or(PromptPayError::MissingField(Field::CountryCode))?;
+
+        let additional_data = match emv::find(&objects, emv::ADDITIONAL_DATA_FIELD_TEMPLATE) {
+            Some(template) => Some(AdditionalData::from_data_objects(&template.children()?)),
+            None => None,
+        };
+        let merchant_information_language =
+            match emv::find(&objects, emv::MERCHANT_INFORMATION_LANGUAGE_TEMPLATE) {
+                Some(template) => Some(MerchantInformationLanguage::from_data_objects(
+                    &template.children()?,
+                )?),
+                None => None,
+            };
+
+        let optional = |tag| find_value(&objects, tag).map(str::to_string);
+
+        Ok(PromptPayPayload {
+            point_of_initiation,
+            proxy,
+            amount,
+            amount_padding,
+            tip_or_convenience_fee,
+            currency,
+            country: country.to_string(),
+            merchant_category_code: optional(emv::MERCHANT_CATEGORY_CODE),
+            merchant_name: optional(emv::MERCHANT_NAME),
+            merchant_city: optional(emv::MERCHANT_CITY),
+            postal_code: optional(emv::POSTAL_CODE),
+            additional_data,
+            merchant_information_language,
+        })
+    }
+}
+
+/// Serialized form of [`PromptPayPayload`], with the amount and fixed fee written as decimal
+/// strings in the payload currency rather than as minor units.
+///
+/// The proxy is a [`Proxy`] for serde, and an [`InputType`] to sanitize for the JavaScript
+/// bindings.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub(crate) struct PayloadRepr<P = Proxy> {
+    /// Defaults to dynamic when there is an amount and static otherwise.
+    #[serde(default)]
+    point_of_initiation: Option<PointOfInitiation>,
+    proxy: P,
+    amount: Option<String>,
+    #[serde(default)]
+    amount_padding: AmountPadding,
+    tip_or_convenience_fee: Option<FeeRepr>,
+    #[serde(default)]
+    currency: Currency,
+    #[serde(default = "default_country")]
+    country: String,
+    merchant_category_code: Option<String>,
+    merchant_name: Option<String>,
+    merchant_city: Option<String>,
+    postal_code: Option<String>,
+    additional_data: Option<AdditionalData>,
+    merchant_information_language: Option<MerchantInformationLanguage>,
+}
+
+/// Serialized form of [`TipOrConvenienceFee`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+enum FeeRepr {
+    PromptForTip,
+    FixedFee(String),
+    PercentageFee(String),
+}
+
+#[cfg(feature = "serde")]
+fn default_country() -> String {
+    "TH".to_string()
+}
+
+/// The proxy of a [`PayloadRepr`].
+#[cfg(feature = "serde")]
+pub(crate) trait ProxyRepr {
+    /// Check or sanitize the proxy.
+    fn into_proxy(self) -> Result<Proxy, PromptPayError>;
+}
+
+#[cfg(feature = "serde")]
+impl ProxyRepr for Proxy {
+    fn into_proxy(self) -> Result<Proxy, PromptPayError> {
+        self.validate()?;
+        Ok(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ProxyRepr for InputType {
+    fn into_proxy(self) -> Result<Proxy, PromptPayError> {
+        Utils::sanitize_input(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<PromptPayPayload> for PayloadRepr {
+    fn from(payload: PromptPayPayload) -> Self {
+        let currency = payload.currency;
+        PayloadRepr {
+            point_of_initiation: Some(payload.point_of_initiation),
+            proxy: payload.proxy,
+            amount: payload.amount.map(|amount| amount.format_in(currency)),
+            amount_padding: payload.amount_padding,
+            tip_or_convenience_fee: payload.tip_or_convenience_fee.map(|fee| match fee {
+                TipOrConvenienceFee::PromptForTip => FeeRepr::PromptForTip,
+                TipOrConvenienceFee::FixedFee(fee) => FeeRepr::FixedFee(fee.format_in(currency)),
+                TipOrConvenienceFee::PercentageFee(percentage) => {
+                    FeeRepr::PercentageFee(percentage.to_string())
+                }
+            }),
+            currency,
+            country: payload.country,
+            merchant_category_code: payload.merchant_category_code,
+            merchant_name: payload.merchant_name,
+            merchant_city: payload.merchant_city,
+            postal_code: payload.postal_code,
+            additional_data: payload.additional_data,
+            merchant_information_language: payload.merchant_information_language,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<P: ProxyRepr> std::convert::TryFrom<PayloadRepr<P>> for PromptPayPayload {
+    type Error = PromptPayError;
+
+    fn try_from(repr: PayloadRepr<P>) -> Result<Self, Self::Error> {
+        let proxy = repr.proxy.into_proxy()?;
+        let currency = repr.currency;
+        let amount = repr
+            .amount
+            .map(|amount| Amount::parse_in(&amount, currency))
+            .transpose()?;
+        let tip_or_convenience_fee = match repr.tip_or_convenience_fee {
+            Some(FeeRepr::PromptForTip) => Some(TipOrConvenienceFee::PromptForTip),
+            Some(FeeRepr::FixedFee(fee)) => Some(TipOrConvenienceFee::FixedFee(Amount::parse_in(
+                &fee, currency,
+            )?)),
+            Some(FeeRepr::PercentageFee(percentage)) => {
+                Some(TipOrConvenienceFee::PercentageFee(percentage.parse()?))
+            }
+            None => None,
+        };
+
+        Ok(PromptPayPayload {
+            point_of_initiation: repr.point_of_initiation.unwrap_or(match amount {
+                Some(_) => PointOfInitiation::Dynamic,
+                None => PointOfInitiation::Static,
+            }),
+            proxy,
+            amount,
+            amount_padding: repr.amount_padding,
+            tip_or_convenience_fee,
+            currency,
+            country: repr.country,
+            merchant_category_code: repr.merchant_category_code,
+            merchant_name: repr.merchant_name,
+            merchant_city: repr.merchant_city,
+            postal_code: repr.postal_code,
+            additional_data: repr.additional_data,
+            merchant_information_language: repr.merchant_information_language,
+        })
+    }
+}
+
+/// The string value of the first data object with the given tag.
+fn find_value<'a>(objects: &'a [DataObject], tag: &str) -> Option<&'a str> {
+    emv::find(objects, tag).and_then(DataObject::as_str)
+}
+
+/// Check the application ID of a merchant account template.
+fn check_application_id(children: &[DataObject], aid: &str) -> Result<(), PromptPayError> {
+    match find_value(children, APPLICATION_ID) {
+        Some(found) if found == aid => Ok(()),
+        Some(found) => Err(PromptPayError::InvalidValue {
+            field: Field::ApplicationId,
+            value: found.to_string(),
+        }),
+        None => Err(PromptPayError::MissingField(Field::ApplicationId)),
+    }
+}
+
+fn parse_credit_transfer(children: &[DataObject]) -> Result<Proxy, PromptPayError> {
+    check_application_id(children, CREDIT_TRANSFER_AID)?;
+
+    if let Some(phone) = find_value(children, PHONE_NUMBER) {
+        Ok(Proxy::PhoneNumber(phone.to_string()))
+    } else if let Some(id) = find_value(children, NATIONAL_ID) {
+        Ok(Proxy::NationalID(id.to_string()))
+    } else if let Some(id) = find_value(children, EWALLET_ID) {
+        Ok(Proxy::EWalletId(id.to_string()))
+    } else if let Some(account) = find_value(children, BANK_ACCOUNT) {
+        // The first three digits are the bank code, the rest is the account number
+        match (account.get(..3), account.get(3..)) {
+            (Some(bank_code), Some(account_number)) if !account_number.is_empty() => {
+                Ok(Proxy::BankAccount {
+                    bank_code: bank_code.to_string(),
+                    account_number: account_number.to_string(),
+                })
+            }
+            _ => Err(PromptPayError::InvalidValue {
+                field: Field::BankAccount,
+                value: account.to_string(),
+            }),
+        }
+    } else {
+        Err(PromptPayError::MissingField(Field::ProxyId))
+    }
+}
+
+fn parse_bill_payment(children: &[DataObject]) -> Result<Proxy, PromptPayError> {
+    check_application_id(children, BILL_PAYMENT_AID)?;
+
+    let biller_id =
+        find_value(children, BILLER_ID).ok_or(PromptPayError::MissingField(Field::BillerId))?;
+    let reference1 =
+        find_value(children, REFERENCE_1).ok_or(PromptPayError::MissingField(Field::Reference1))?;
+
+    Ok(Proxy::BillPayment {
+        biller_id: biller_id.to_string(),
+        reference1: reference1.to_string(),
+        reference2: find_value(children, REFERENCE_2).map(str::to_string),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Percentage, PointOfInitiation, PromptPayPayload, Proxy, QrMode, TipOrConvenienceFee,
+    };
+    use crate::additional_data::AdditionalData;
+    use crate::amount::{Amount, AmountPadding};
+    use crate::currency::Currency;
+    use crate::error::{Field, PromptPayError};
+    use crate::language::MerchantInformationLanguage;
+    use crate::promptpay_utils::{InputType, Utils};
+
+    /// Append the CRC to a payload body ending in "6304".
+    fn with_crc(data: &str) -> String {
+        format!("{}{}", data, Utils::calculate_precise_crc(data))
+    }
+
+    #[test]
+    fn test_parse_phone_number_payload() {
+        let input = InputType::PhoneNumber("0812345678".to_string());
+        let payload = Utils::generate_payload(input, 123.45).unwrap();
+        let parsed = PromptPayPayload::parse(&payload).unwrap();
+        assert_eq!(parsed.point_of_initiation, PointOfInitiation::Dynamic);
+        assert_eq!(
+            parsed.proxy,
+            Proxy::PhoneNumber("0066812345678".to_string())
+        );
+        assert_eq!(
+            parsed.amount,
+            Some(Amount::from_minor_units(12_345).unwrap())
+        );
+        assert_eq!(parsed.currency, Currency::THB);
+        assert_eq!(parsed.country, "TH");
+    }
+
+    #[test]
+    fn test_parse_ewallet_payload() {
+        let payload =
+            with_crc("00020101021229390016A000000677010111031514000000123456753037645802TH6304");
+        let parsed = PromptPayPayload::parse(&payload).unwrap();
+        assert_eq!(parsed.point_of_initiation, PointOfInitiation::Dynamic);
+        assert_eq!(
+            parsed.proxy,
+            Proxy::EWalletId("140000001234567".to_string())
+        );
+        assert_eq!(parsed.amount, None);
+    }
+
+    #[test]
+    fn test_parse_bill_payment_payload() {
+        let payload = with_crc(
+            "00020101021230630016A00000067701011201150107536000315080206INV0010310CUSTOMER01\
+             53037645406500.005802TH6304",
+        );
+        let parsed = PromptPayPayload::parse(&payload).unwrap();
+        assert_eq!(
+            parsed.proxy,
+            Proxy::BillPayment {
+                biller_id: "010753600031508".to_string(),
+                reference1: "INV001".to_string(),
+                reference2: Some("CUSTOMER01".to_string()),
+            }
+        );
+        assert_eq!(
+            parsed.amount,
+            Some(Amount::from_minor_units(50_000).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_crc_mismatch() {
+        let input = InputType::NationalID("1234567890121".to_string());
+        let mut payload = Utils::generate_payload(input, 10.0).unwrap();
+        payload.replace_range(payload.len() - 4.., "0000");
+        let result = PromptPayPayload::parse(&payload);
+        assert!(matches!(
+            result.unwrap_err(),
+            PromptPayError::CrcMismatch { actual, .. } if actual == "0000"
+        ));
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let proxy = Proxy::BillPayment {
+            biller_id: "010753600031508".to_string(),
+            reference1: "INV001".to_string(),
+            reference2: None,
+        };
+        let payload = PromptPayPayload::new(proxy, QrMode::Static);
+        let encoded = payload.encode().unwrap();
+        assert_eq!(PromptPayPayload::parse(&encoded).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_parse_bank_account_payload() {
+        let input = InputType::BankAccount {
+            bank_code: "014".to_string(),
+            account_number: "1234567890".to_string(),
+        };
+        let payload = Utils::generate_payload(input, 99.5).unwrap();
+        let parsed = PromptPayPayload::parse(&payload).unwrap();
+        assert_eq!(
+            parsed.proxy,
+            Proxy::BankAccount {
+                bank_code: "014".to_string(),
+                account_number: "1234567890".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_additional_data_round_trip() {
+        let mut payload = PromptPayPayload::new(
+            Proxy::PhoneNumber("0066812345678".to_string()),
+            QrMode::Dynamic(Amount::from_minor_units(25_000).unwrap()),
+        );
+        payload.additional_data = Some(
+            AdditionalData::new()
+                .bill_number("INV001")
+                .store_label("BRANCH12")
+                .terminal_label("POS3"),
+        );
+        let encoded = payload.encode().unwrap();
+        assert!(encoded.contains("62300106INV0010308BRANCH120704POS36304"));
+        assert_eq!(PromptPayPayload::parse(&encoded).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_merchant_fields_round_trip() {
+        let mut payload = PromptPayPayload::new(
+            Proxy::NationalID("1234567890121".to_string()),
+            QrMode::Static,
+        );
+        payload.merchant_category_code = Some("5812".to_string());
+        payload.merchant_name = Some("SOMTAM SHOP".to_string());
+        payload.merchant_city = Some("BANGKOK".to_string());
+        payload.postal_code = Some("10110".to_string());
+        let encoded = payload.encode().unwrap();
+        assert!(encoded.contains("520458125303764"));
+        assert!(encoded.contains("5802TH5911SOMTAM SHOP6007BANGKOK610510110"));
+        assert_eq!(PromptPayPayload::parse(&encoded).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_merchant_information_language_round_trip() {
+        let mut payload = PromptPayPayload::new(
+            Proxy::PhoneNumber("0066812345678".to_string()),
+            QrMode::Static,
+        );
+        payload.merchant_name = Some("SOMTAM SHOP".to_string());
+        payload.merchant_information_language =
+            Some(MerchantInformationLanguage::new("th", "ร้านส้มตำ").merchant_city("กรุงเทพฯ"));
+        let encoded = payload.encode().unwrap();
+        assert!(encoded.contains("64310002th0109ร้านส้มตำ0208กรุงเทพฯ6304"));
+        assert_eq!(PromptPayPayload::parse(&encoded).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_currency_and_country_round_trip() {
+        let yen = Currency::from_alpha("JPY").unwrap();
+        let mut payload = PromptPayPayload::new(
+            Proxy::PhoneNumber("0066812345678".to_string()),
+            QrMode::Dynamic(Amount::parse_in("1500", yen).unwrap()),
+        );
+        payload.currency = yen;
+        payload.country = "JP".to_string();
+        payload.tip_or_convenience_fee = Some(TipOrConvenienceFee::FixedFee(
+            Amount::parse_in("100", yen).unwrap(),
+        ));
+        let encoded = payload.encode().unwrap();
+        assert!(encoded.contains("530339254041500550202560310"));
+        assert!(encoded.contains("5802JP"));
+        assert_eq!(PromptPayPayload::parse(&encoded).unwrap(), payload);
+
+        payload.country = "Japan".to_string();
+        assert!(payload.encode().is_err());
+    }
+
+    #[test]
+    fn test_merchant_fields_validation() {
+        let mut payload = PromptPayPayload::new(
+            Proxy::NationalID("1234567890121".to_string()),
+            QrMode::Static,
+        );
+        payload.merchant_city = Some("KRUNG THEP MAHA NAKHON".to_string());
+        assert_eq!(
+            payload.encode().unwrap_err(),
+            PromptPayError::LengthOutOfRange {
+                field: Field::MerchantCity,
+                min: 1,
+                max: 15,
+                actual: 22
+            }
+        );
+        payload.merchant_city = None;
+        payload.merchant_category_code = Some("58A2".to_string());
+        assert!(payload.encode().is_err());
+    }
+
+    #[test]
+    fn test_tip_or_convenience_fee_round_trip() {
+        let mut payload = PromptPayPayload::new(
+            Proxy::PhoneNumber("0066812345678".to_string()),
+            QrMode::Dynamic(Amount::from_minor_units(10_000).unwrap()),
+        );
+        for (fee, encoded_fee) in [
+            (TipOrConvenienceFee::PromptForTip, "550201"),
+            (
+                TipOrConvenienceFee::FixedFee(Amount::from_minor_units(1000).unwrap()),
+                "5502025605",
+            ),
+            (
+                TipOrConvenienceFee::PercentageFee(Percentage::from_hundredths(350).unwrap()),
+                "5502035704",
+            ),
+        ] {
+            payload.tip_or_convenience_fee = Some(fee);
+            let encoded = payload.encode().unwrap();
+            assert!(encoded.contains(encoded_fee));
+            assert_eq!(PromptPayPayload::parse(&encoded).unwrap(), payload);
+        }
+
+        payload.tip_or_convenience_fee = Some(TipOrConvenienceFee::PercentageFee(Percentage::MAX));
+        assert!(payload.encode().is_err());
+    }
+
+    #[test]
+    fn test_percentage_range() {
+        assert_eq!(Percentage::from_hundredths(10_000), Ok(Percentage::MAX));
+        assert!(Percentage::from_hundredths(10_001).is_err());
+
+        assert_eq!("0".parse::<Percentage>().unwrap().hundredths(), 0);
+        assert_eq!("99.99".parse::<Percentage>().unwrap().hundredths(), 9_999);
+        for invalid in ["", ".5", "-1", "1.234", "100.01", "250", "1e2", "3.5%"] {
+            assert!(invalid.parse::<Percentage>().is_err(), "{}", invalid);
+        }
+
+        assert_eq!(Percentage::try_from(3.456).unwrap().hundredths(), 346);
+        assert!(Percentage::try_from(-0.5).is_err());
+        assert!(Percentage::try_from(120.0).is_err());
+        assert!(Percentage::try_from(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_parse_percentage_fee_out_of_range() {
+        for percentage in ["040.00", "06100.00"] {
+            let payload = with_crc(&format!(
+                "00020101021229370016A000000677010111011300668123456785303764540\
+                 6100.0055020357{}5802TH6304",
+                percentage
+            ));
+            assert_eq!(
+                PromptPayPayload::parse(&payload).unwrap_err(),
+                PromptPayError::InvalidValue {
+                    field: Field::ConvenienceFeePercentage,
+                    value: percentage[2..].to_string(),
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_inconsistent_convenience_fee() {
+        // Indicator "02" (fixed fee) with a percentage fee instead of tag 56
+        let payload = with_crc(
+            "00020101021229370016A000000677010111011300668123456785303764540\
+             6100.0055020257043.505802TH6304",
+        );
+        let result = PromptPayPayload::parse(&payload);
+        assert_eq!(
+            result.unwrap_err(),
+            PromptPayError::InconsistentConvenienceFee
+        );
+    }
+
+    #[test]
+    fn test_amount_padding_round_trip() {
+        let mut payload = PromptPayPayload::new(
+            Proxy::PhoneNumber("0066812345678".to_string()),
+            QrMode::Dynamic(Amount::from_minor_units(12_345).unwrap()),
+        );
+        assert!(payload.encode().unwrap().contains("5406123.45"));
+
+        payload.amount_padding = AmountPadding::ZeroPadded(9);
+        let encoded = payload.encode().unwrap();
+        assert!(encoded.contains("5409000123.45"));
+        assert_eq!(PromptPayPayload::parse(&encoded).unwrap(), payload);
+
+        payload.amount_padding = AmountPadding::ZeroPadded(20);
+        assert!(payload.encode().is_err());
+    }
+
+    #[test]
+    fn test_large_amount() {
+        let payload = PromptPayPayload::new(
+            Proxy::NationalID("1234567890121".to_string()),
+            QrMode::Dynamic("1,250,000.00".parse().unwrap()),
+        );
+        let encoded = payload.encode().unwrap();
+        assert!(encoded.contains("54101250000.00"));
+        assert_eq!(PromptPayPayload::parse(&encoded).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_encode_dynamic_requires_amount() {
+        let mut payload = PromptPayPayload::new(
+            Proxy::NationalID("1234567890121".to_string()),
+            QrMode::Dynamic(Amount::from_minor_units(5000).unwrap()),
+        );
+        payload.amount = None;
+        assert!(payload.encode().is_err());
+    }
+
+    #[test]
+    fn test_parse_missing_merchant_account() {
+        let payload = with_crc("0002010102115802TH53037646304");
+        assert!(PromptPayPayload::parse(&payload).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_payload_serde() {
+        let mut payload = PromptPayPayload::new(
+            Proxy::PhoneNumber("0066812345678".to_string()),
+            QrMode::Dynamic(Amount::from_minor_units(12_345).unwrap()),
+        );
+        payload.tip_or_convenience_fee = Some(TipOrConvenienceFee::FixedFee(
+            Amount::from_minor_units(1000).unwrap(),
+        ));
+        payload.additional_data = Some(AdditionalData::new().bill_number("INV001"));
+
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(json["point_of_initiation"], "dynamic");
+        assert_eq!(
+            json["proxy"],
+            serde_json::json!({"type": "phone", "value": "0066812345678"})
+        );
+        assert_eq!(json["amount"], "123.45");
+        assert_eq!(
+            json["tip_or_convenience_fee"],
+            serde_json::json!({"type": "fixed_fee", "value": "10.00"})
+        );
+        assert_eq!(json["currency"], "THB");
+        assert_eq!(json["additional_data"]["bill_number"], "INV001");
+        assert_eq!(
+            serde_json::from_value::<PromptPayPayload>(json).unwrap(),
+            payload
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_payload_serde_defaults() {
+        // Amounts are written with the decimals of the currency
+        let json = r#"{"proxy":{"type":"national_id","value":"1234567890121"},
+                       "amount":"500","currency":"JPY"}"#;
+        let payload: PromptPayPayload = serde_json::from_str(json).unwrap();
+        assert_eq!(payload.point_of_initiation, PointOfInitiation::Dynamic);
+        assert_eq!(payload.amount, Some(Amount::from_minor_units(500).unwrap()));
+        assert_eq!(payload.country, "TH");
+        assert!(payload.encode().unwrap().contains("5303392540350058"));
+
+        let json = r#"{"proxy":{"type":"ewallet_id","value":"123456789012345"}}"#;
+        let payload: PromptPayPayload = serde_json::from_str(json).unwrap();
+        assert_eq!(payload.point_of_initiation, PointOfInitiation::Static);
+        assert_eq!(payload.currency, Currency::THB);
+
+        let json = r#"{"proxy":{"type":"phone","value":"0066812345678"},"amount":"1.005"}"#;
+        assert!(serde_json::from_str::<PromptPayPayload>(json).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_payload_serde_rejects_invalid_proxy() {
+        // Wrong check digit
+        let json = r#"{"proxy":{"type":"national_id","value":"1234567890120"}}"#;
+        let err = serde_json::from_str::<PromptPayPayload>(json).unwrap_err();
+        assert!(err.to_string().contains("check digit"), "{}", err);
+
+        // Valid, but not the "0066" proxy form the payload needs
+        let json = r#"{"proxy":{"type":"phone","value":"0812345678"}}"#;
+        assert!(serde_json::from_str::<PromptPayPayload>(json).is_err());
+    }
+
+    #[test]
+    fn test_encode_validates_proxy() {
+        let mut payload = PromptPayPayload::new(
+            Proxy::NationalID("1234567890120".to_string()),
+            QrMode::Static,
+        );
+        assert_eq!(
+            payload.encode().unwrap_err(),
+            PromptPayError::InvalidCheckDigit {
+                field: Field::NationalID,
+                expected: '1',
+                actual: '0',
+            }
+        );
+
+        payload.proxy = Proxy::PhoneNumber("081-234-5678".to_string());
+        assert_eq!(
+            payload.encode().unwrap_err(),
+            PromptPayError::InvalidValue {
+                field: Field::PhoneNumber,
+                value: "081-234-5678".to_string(),
+            }
+        );
+
+        payload.proxy = Proxy::BillPayment {
+            biller_id: "010753600031508".to_string(),
+            reference1: "INV001".to_string(),
+            reference2: Some("ref2".to_string()),
+        };
+        assert_eq!(
+            payload.encode().unwrap_err(),
+            PromptPayError::InvalidValue {
+                field: Field::Reference2,
+                value: "ref2".to_string(),
+            }
+        );
+        payload.proxy = Proxy::BillPayment {
+            biller_id: "010753600031508".to_string(),
+            reference1: "INV001".to_string(),
+            reference2: Some("REF2".to_string()),
+        };
+        assert!(payload.encode().is_ok());
+    }
+}
@@ -0,0 +1,115 @@
+use qrcode::{Color, EcLevel, QrCode};
+
+use crate::error::PromptPayError;
+
+#[cfg(feature = "png")]
+pub mod png;
+#[cfg(feature = "svg")]
+pub mod svg;
+#[cfg(feature = "terminal")]
+pub mod terminal;
+
+/// How much of a QR code can be damaged or covered before it stops scanning. Higher levels
+/// make the code denser.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ErrorCorrection {
+    /// Recovers about 7% of the code.
+    Low,
+    /// Recovers about 15% of the code.
+    #[default]
+    Medium,
+    /// Recovers about 25% of the code.
+    Quartile,
+    /// Recovers about 30% of the code, e.g. to leave room for a logo.
+    High,
+}
+
+impl From<ErrorCorrection> for EcLevel {
+    fn from(level: ErrorCorrection) -> Self {
+        match level {
+            ErrorCorrection::Low => EcLevel::L,
+            ErrorCorrection::Medium => EcLevel::M,
+            ErrorCorrection::Quartile => EcLevel::Q,
+            ErrorCorrection::High => EcLevel::H,
+        }
+    }
+}
+
+/// The modules of a QR code, without the quiet zone.
+pub(crate) struct Matrix {
+    width: usize,
+    modules: Vec<bool>,
+}
+
+impl Matrix {
+    /// Encode a payload string as a QR code.
+    ///
+    /// # Returns
+    /// The QR code, or an error if the payload does not fit at the requested
+    /// error-correction level.
+    pub(crate) fn new(
+        payload: &str,
+        error_correction: ErrorCorrection,
+    ) -> Result<Self, PromptPayError> {
+        let code = QrCode::with_error_correction_level(payload, error_correction.into())
+            .map_err(|_| PromptPayError::PayloadTooLong)?;
+        Ok(Matrix {
+            width: code.width(),
+            modules: code
+                .to_colors()
+                .into_iter()
+                .map(|color| color == Color::Dark)
+                .collect(),
+        })
+    }
+
+    /// The number of modules along each side.
+    pub(crate) fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of modules along each side with `quiet_zone` modules of margin on both
+    /// sides.
+    ///
+    /// # Returns
+    /// The width, or an error if it does not fit in a `usize`.
+    #[cfg(any(feature = "svg", feature = "terminal"))]
+    pub(crate) fn width_with_quiet_zone(&self, quiet_zone: u32) -> Result<usize, PromptPayError> {
+        usize::try_from(quiet_zone)
+            .ok()
+            .and_then(|quiet_zone| quiet_zone.checked_mul(2))
+            .and_then(|quiet_zones| quiet_zones.checked_add(self.width))
+            .ok_or(PromptPayError::ImageTooLarge)
+    }
+
+    /// Whether the module at column `x` and row `y` is dark.
+    pub(crate) fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.width + x]
+    }
+}
+
+#[cfg(all(test, any(feature = "svg", feature = "terminal")))]
+mod tests {
+    use super::Matrix;
+    use crate::error::PromptPayError;
+
+    #[test]
+    fn test_width_with_quiet_zone() {
+        let matrix = Matrix {
+            width: 21,
+            modules: Vec::new(),
+        };
+        assert_eq!(matrix.width_with_quiet_zone(4), Ok(29));
+
+        let matrix = Matrix {
+            width: usize::MAX - 1,
+            modules: Vec::new(),
+        };
+        assert_eq!(
+            matrix.width_with_quiet_zone(1),
+            Err(PromptPayError::ImageTooLarge)
+        );
+    }
+}
@@ -0,0 +1,689 @@
+use std::borrow::Cow;
+
+use ab_glyph::{point, Font, FontRef, GlyphId, PxScale, ScaleFont};
+use rustybuzz::UnicodeBuffer;
+
+use super::{ErrorCorrection, Matrix};
+use crate::error::PromptPayError;
+
+/// Colour of the Thai QR Payment header and border.
+const BRAND_BLUE: [u8; 3] = [0x0e, 0x3d, 0x67];
+/// Colour of the PromptPay wordmark.
+const PROMPTPAY_BLUE: [u8; 3] = [0x1b, 0x4d, 0x9c];
+const WHITE: [u8; 3] = [0xff, 0xff, 0xff];
+const BLACK: [u8; 3] = [0x00, 0x00, 0x00];
+const TEXT_GREY: [u8; 3] = [0x33, 0x33, 0x33];
+
+/// The largest width or height a PNG image may have.
+const MAX_DIMENSION: u64 = (1 << 31) - 1;
+
+/// The font [`FrameOptions::default`] draws text with: Latin and Thai from Go Noto Universal
+/// (Noto Sans and Noto Sans Thai), licensed under the SIL Open Font License 1.1.
+pub const THAI_FONT: &[u8] = include_bytes!("fonts/GoNotoKurrent-Subset.ttf");
+
+/// Appearance of a bare PNG QR code.
+///
+/// ```
+/// use prompt_pay::render::png::PngOptions;
+///
+/// let options = PngOptions::new().module_size(4).dark_color([0x1a, 0x23, 0x7e]);
+/// assert_eq!(options.quiet_zone, 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PngOptions {
+    /// Size of one module in pixels.
+    pub module_size: u32,
+    /// Width of the blank border around the code, in modules. Scanners expect at least 4.
+    pub quiet_zone: u32,
+    /// RGB colour of the dark modules.
+    pub dark_color: [u8; 3],
+    /// RGB colour of the light modules and the quiet zone.
+    pub light_color: [u8; 3],
+    /// Error-correction level of the code.
+    pub error_correction: ErrorCorrection,
+}
+
+impl Default for PngOptions {
+    fn default() -> Self {
+        PngOptions {
+            module_size: 8,
+            quiet_zone: 4,
+            dark_color: BLACK,
+            light_color: WHITE,
+            error_correction: ErrorCorrection::Medium,
+        }
+    }
+}
+
+impl PngOptions {
+    /// Black on white, 8 pixels per module, with a 4-module quiet zone.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the size of one module in pixels.
+    pub fn module_size(mut self, pixels: u32) -> Self {
+        self.module_size = pixels;
+        self
+    }
+
+    /// Set the width of the quiet zone in modules.
+    pub fn quiet_zone(mut self, modules: u32) -> Self {
+        self.quiet_zone = modules;
+        self
+    }
+
+    /// Set the colour of the dark modules.
+    pub fn dark_color(mut self, color: [u8; 3]) -> Self {
+        self.dark_color = color;
+        self
+    }
+
+    /// Set the colour of the light modules and the quiet zone.
+    pub fn light_color(mut self, color: [u8; 3]) -> Self {
+        self.light_color = color;
+        self
+    }
+
+    /// Set the error-correction level.
+    pub fn error_correction(mut self, level: ErrorCorrection) -> Self {
+        self.error_correction = level;
+        self
+    }
+}
+
+/// Contents and fonts of a Thai QR Payment frame: a blue header, the PromptPay wordmark,
+/// the QR code, and the payee name and amount underneath.
+///
+/// The default options draw text with the bundled [`THAI_FONT`], which covers Thai and
+/// Western European Latin text. Use [`FrameOptions::new`] for other TrueType or OpenType
+/// fonts, e.g. Sarabun. Text is shaped with OpenType layout, so Thai vowels and tone marks
+/// stack above and below their consonant.
+///
+/// ```
+/// use prompt_pay::render::png::FrameOptions;
+///
+/// let frame = FrameOptions::default()
+///     .payee_name("ร้านส้มตำป้าแดง")
+///     .amount("250.00 THB");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameOptions {
+    /// Fonts to draw text with, tried in order for each character.
+    pub fonts: Vec<Cow<'static, [u8]>>,
+    /// Payee name shown under the code.
+    pub payee_name: Option<String>,
+    /// Amount shown under the payee name, e.g. "250.00 THB".
+    pub amount: Option<String>,
+    /// Size of one module in pixels; the frame scales with it.
+    pub module_size: u32,
+    /// Error-correction level of the code.
+    pub error_correction: ErrorCorrection,
+}
+
+impl Default for FrameOptions {
+    fn default() -> Self {
+        FrameOptions::new(THAI_FONT)
+    }
+}
+
+impl FrameOptions {
+    /// Frame text drawn with `font`, 8 pixels per module.
+    pub fn new(font: impl Into<Cow<'static, [u8]>>) -> Self {
+        FrameOptions {
+            fonts: vec![font.into()],
+            payee_name: None,
+            amount: None,
+            module_size: 8,
+            error_correction: ErrorCorrection::Medium,
+        }
+    }
+
+    /// Add a font for characters the fonts before it do not cover.
+    pub fn fallback_font(mut self, font: impl Into<Cow<'static, [u8]>>) -> Self {
+        self.fonts.push(font.into());
+        self
+    }
+
+    /// Set the payee name shown under the code.
+    pub fn payee_name(mut self, name: impl Into<String>) -> Self {
+        self.payee_name = Some(name.into());
+        self
+    }
+
+    /// Set the amount shown under the payee name.
+    pub fn amount(mut self, amount: impl Into<String>) -> Self {
+        self.amount = Some(amount.into());
+        self
+    }
+
+    /// Set the size of one module in pixels.
+    pub fn module_size(mut self, pixels: u32) -> Self {
+        self.module_size = pixels;
+        self
+    }
+
+    /// Set the error-correction level.
+    pub fn error_correction(mut self, level: ErrorCorrection) -> Self {
+        self.error_correction = level;
+        self
+    }
+}
+
+/// Render a payload string as a bare PNG QR code.
+///
+/// # Parameters
+/// - `payload`: The payload string to encode.
+/// - `options`: Size, colours and error-correction level of the code.
+///
+/// # Returns
+/// The PNG file contents, or an error if the payload does not fit in a QR code or the image
+/// would be too large.
+pub fn to_png(payload: &str, options: &PngOptions) -> Result<Vec<u8>, PromptPayError> {
+    let matrix = Matrix::new(payload, options.error_correction)?;
+    let module_size = u64::from(options.module_size);
+    let quiet_zone = u64::from(options.quiet_zone) * module_size;
+    let size = quiet_zone
+        .checked_mul(2)
+        .and_then(|quiet_zones| quiet_zones.checked_add(matrix.width() as u64 * module_size))
+        .ok_or(PromptPayError::ImageTooLarge)
+        .and_then(dimension)?;
+
+    // The quiet zone is narrower than the image, so it fits in a u32 as well
+    let quiet_zone = quiet_zone as u32;
+    let mut canvas = Canvas::new(size, size, options.light_color)?;
+    canvas.draw_matrix(
+        &matrix,
+        quiet_zone,
+        quiet_zone,
+        options.module_size,
+        options.dark_color,
+    );
+    canvas.encode()
+}
+
+/// Render a payload string as a PNG QR code inside a Thai QR Payment frame.
+///
+/// # Parameters
+/// - `payload`: The payload string to encode.
+/// - `options`: Text, fonts and size of the frame.
+///
+/// # Returns
+/// The PNG file contents, or an error if the payload does not fit in a QR code, a font
+/// cannot be read, no font has a glyph for a character of the text or the image would be
+/// too large.
+pub fn to_framed_png(payload: &str, options: &FrameOptions) -> Result<Vec<u8>, PromptPayError> {
+    let fonts = options
+        .fonts
+        .iter()
+        .map(|font| TextFont::new(font))
+        .collect::<Result<Vec<_>, _>>()?;
+    let matrix = Matrix::new(payload, options.error_correction)?;
+
+    // Every measurement is a multiple of the module size so the frame scales with the code.
+    // The module count is at most 177 and the multipliers are small, so the layout cannot
+    // overflow a u64; only the image size is checked.
+    let m = u64::from(options.module_size);
+    let quiet_zone = 2;
+    let qr_size = (matrix.width() as u64 + 2 * quiet_zone) * m;
+    let padding = 3 * m;
+    let header_height = 12 * m;
+    let wordmark_height = 10 * m;
+
+    let mut lines = Vec::new();
+    if let Some(payee_name) = &options.payee_name {
+        lines.push((payee_name.as_str(), 4.5 * m as f32, TEXT_GREY));
+    }
+    if let Some(amount) = &options.amount {
+        lines.push((amount.as_str(), 6.5 * m as f32, BRAND_BLUE));
+    }
+    let text_height: f32 = lines.iter().map(|(_, size, _)| size * 1.5).sum();
+    let width = dimension(qr_size + 2 * padding)?;
+    let height =
+        dimension(header_height + wordmark_height + qr_size + text_height.ceil() as u64 + 3 * m)?;
+
+    // Every measurement below is smaller than the height, so it fits in a u32 as well
+    let (m, quiet_zone, qr_size, padding) =
+        (m as u32, quiet_zone as u32, qr_size as u32, padding as u32);
+    let (header_height, wordmark_height) = (header_height as u32, wordmark_height as u32);
+    let border = m;
+
+    let mut canvas = Canvas::new(width, height, WHITE)?;
+    canvas.fill_rect(0, 0, width, header_height, BRAND_BLUE);
+    let text_width = (width - 2 * padding) as f32;
+    canvas.draw_text(
+        &fonts,
+        "THAI QR PAYMENT",
+        5.0 * m as f32,
+        text_width,
+        header_height as f32 * 0.68,
+        WHITE,
+    )?;
+    canvas.draw_text(
+        &fonts,
+        "PromptPay",
+        7.0 * m as f32,
+        text_width,
+        (header_height + wordmark_height) as f32 * 0.9,
+        PROMPTPAY_BLUE,
+    )?;
+
+    let qr_top = header_height + wordmark_height;
+    canvas.draw_matrix(
+        &matrix,
+        padding + quiet_zone * m,
+        qr_top + quiet_zone * m,
+        m,
+        BLACK,
+    );
+
+    let mut baseline = (qr_top + qr_size) as f32;
+    for (text, size, color) in lines {
+        baseline += size * 1.5;
+        canvas.draw_text(&fonts, text, size, text_width, baseline - size * 0.4, color)?;
+    }
+
+    // Frame the sides and bottom in the header colour
+    canvas.fill_rect(0, header_height, border, height - header_height, BRAND_BLUE);
+    canvas.fill_rect(
+        width - border,
+        header_height,
+        border,
+        height - header_height,
+        BRAND_BLUE,
+    );
+    canvas.fill_rect(0, height - border, width, border, BRAND_BLUE);
+
+    canvas.encode()
+}
+
+/// Check that an image width or height in pixels can be encoded as PNG.
+fn dimension(pixels: u64) -> Result<u32, PromptPayError> {
+    if pixels > MAX_DIMENSION {
+        return Err(PromptPayError::ImageTooLarge);
+    }
+    Ok(pixels as u32)
+}
+
+/// An RGB image being drawn.
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    /// Allocate an image filled with `background`.
+    ///
+    /// # Returns
+    /// The image, or an error if its pixels do not fit in memory.
+    fn new(width: u32, height: u32, background: [u8; 3]) -> Result<Self, PromptPayError> {
+        let length = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|pixels| pixels.checked_mul(background.len()))
+            .ok_or(PromptPayError::ImageTooLarge)?;
+
+        let mut pixels = Vec::new();
+        pixels
+            .try_reserve_exact(length)
+            .map_err(|_| PromptPayError::ImageTooLarge)?;
+        pixels.extend(background.iter().cycle().take(length));
+        Ok(Canvas {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Blend `color` over the pixel at (x, y) with the given coverage from 0 to 1.
+    fn blend(&mut self, x: u32, y: u32, color: [u8; 3], coverage: f32) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let coverage = coverage.clamp(0.0, 1.0);
+        let offset = (y as usize * self.width as usize + x as usize) * 3;
+        for (pixel, channel) in self.pixels[offset..offset + 3].iter_mut().zip(color) {
+            *pixel = (f32::from(*pixel) * (1.0 - coverage) + f32::from(channel) * coverage).round()
+                as u8;
+        }
+    }
+
+    fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: [u8; 3]) {
+        for row in y..y.saturating_add(height).min(self.height) {
+            for column in x..x.saturating_add(width).min(self.width) {
+                self.blend(column, row, color, 1.0);
+            }
+        }
+    }
+
+    /// Draw the dark modules of a QR code with its top left corner at (left, top).
+    fn draw_matrix(
+        &mut self,
+        matrix: &Matrix,
+        left: u32,
+        top: u32,
+        module_size: u32,
+        color: [u8; 3],
+    ) {
+        for y in 0..matrix.width() {
+            for x in 0..matrix.width() {
+                if matrix.is_dark(x, y) {
+                    self.fill_rect(
+                        left + x as u32 * module_size,
+                        top + y as u32 * module_size,
+                        module_size,
+                        module_size,
+                        color,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Draw a line of text centred horizontally, shrinking it to fit within `max_width`.
+    fn draw_text(
+        &mut self,
+        fonts: &[TextFont<'_>],
+        text: &str,
+        size: f32,
+        max_width: f32,
+        baseline: f32,
+        color: [u8; 3],
+    ) -> Result<(), PromptPayError> {
+        let glyphs = shape(fonts, text)?;
+        let measure = |size: f32| -> f32 {
+            glyphs
+                .iter()
+                .map(|glyph| glyph.x_advance * glyph.font.scale(size))
+                .sum()
+        };
+
+        let width = measure(size);
+        let size = if width > max_width {
+            size * max_width / width
+        } else {
+            size
+        };
+        let mut x = (self.width as f32 - measure(size)) / 2.0;
+
+        for glyph in &glyphs {
+            let scale = glyph.font.scale(size);
+            let position = point(
+                x + glyph.x_offset * scale,
+                baseline - glyph.y_offset * scale,
+            );
+            let outline = glyph
+                .font
+                .outlines
+                .outline_glyph(glyph.id.with_scale_and_position(size, position));
+            if let Some(outline) = outline {
+                let bounds = outline.px_bounds();
+                outline.draw(|gx, gy, coverage| {
+                    let px = bounds.min.x + gx as f32;
+                    let py = bounds.min.y + gy as f32;
+                    if px >= 0.0 && py >= 0.0 {
+                        self.blend(px as u32, py as u32, color, coverage);
+                    }
+                });
+            }
+            x += glyph.x_advance * scale;
+        }
+        Ok(())
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, PromptPayError> {
+        let mut output = Vec::new();
+        let mut encoder = png::Encoder::new(&mut output, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let result = encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.pixels));
+        result.map_err(|error| PromptPayError::ImageEncoding(error.to_string()))?;
+        Ok(output)
+    }
+}
+
+/// A font parsed both for shaping text and for drawing its glyphs.
+struct TextFont<'a> {
+    outlines: FontRef<'a>,
+    shaper: rustybuzz::Face<'a>,
+    /// Whether the font positions marks itself with a GPOS table.
+    positions_marks: bool,
+}
+
+impl<'a> TextFont<'a> {
+    fn new(data: &'a [u8]) -> Result<Self, PromptPayError> {
+        let outlines = FontRef::try_from_slice(data).map_err(|_| PromptPayError::InvalidFont)?;
+        let shaper = rustybuzz::Face::from_slice(data, 0).ok_or(PromptPayError::InvalidFont)?;
+        let positions_marks = shaper.tables().gpos.is_some();
+        Ok(TextFont {
+            outlines,
+            shaper,
+            positions_marks,
+        })
+    }
+
+    fn has_glyph(&self, c: char) -> bool {
+        self.outlines.glyph_id(c).0 != 0
+    }
+
+    /// Pixels per font unit when drawing at `size`.
+    fn scale(&self, size: f32) -> f32 {
+        self.outlines
+            .as_scaled(PxScale::from(size))
+            .h_scale_factor()
+    }
+}
+
+/// A glyph placed by the shaper, with its advance and offsets in font units.
+struct ShapedGlyph<'a> {
+    font: &'a TextFont<'a>,
+    id: GlyphId,
+    x_advance: f32,
+    x_offset: f32,
+    y_offset: f32,
+}
+
+/// Shape a line of text, drawing each character with the first font that has a glyph for
+/// it so text never renders as empty boxes.
+///
+/// # Returns
+/// The glyphs in drawing order, or an error if no font has a glyph for a character.
+fn shape<'a>(
+    fonts: &'a [TextFont<'a>],
+    text: &str,
+) -> Result<Vec<ShapedGlyph<'a>>, PromptPayError> {
+    // Split the text into runs of the same font. Combining marks stay in the run of their
+    // base character so the shaper can place them over it.
+    let mut runs: Vec<(usize, String)> = Vec::new();
+    for c in text.chars() {
+        let current = runs.last().map(|(font, _)| *font);
+        let font = match current {
+            Some(font) if is_combining_mark(c) && fonts[font].has_glyph(c) => font,
+            _ => match fonts.iter().position(|font| font.has_glyph(c)) {
+                Some(font) => font,
+                None if c.is_whitespace() => current.unwrap_or(0),
+                None => return Err(PromptPayError::MissingGlyph(c)),
+            },
+        };
+        match runs.last_mut() {
+            Some((current, run)) if *current == font => run.push(c),
+            _ => runs.push((font, c.to_string())),
+        }
+    }
+
+    let mut glyphs = Vec::new();
+    for (font, run) in runs {
+        let font = &fonts[font];
+        let mut buffer = UnicodeBuffer::new();
+        buffer.push_str(&run);
+        if !font.positions_marks {
+            // Without GPOS the Thai shaper leaves marks where the font draws them, which puts
+            // tone marks on top of upper vowels. The generic shaper places marks above or
+            // below their base from the glyph outlines instead.
+            buffer.set_script(rustybuzz::script::COMMON);
+        }
+        let shaped = rustybuzz::shape(&font.shaper, &[], buffer);
+        for (info, position) in shaped.glyph_infos().iter().zip(shaped.glyph_positions()) {
+            glyphs.push(ShapedGlyph {
+                font,
+                id: GlyphId(info.glyph_id as u16),
+                x_advance: position.x_advance as f32,
+                x_offset: position.x_offset as f32,
+                y_offset: position.y_offset as f32,
+            });
+        }
+    }
+    Ok(glyphs)
+}
+
+/// Whether `c` is a combining mark drawn over or under the character before it, such as a
+/// Thai vowel or tone mark.
+fn is_combining_mark(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036f}'
+            | '\u{0e31}'
+            | '\u{0e34}'..='\u{0e3a}'
+            | '\u{0e47}'..='\u{0e4e}'
+            | '\u{1ab0}'..='\u{1aff}'
+            | '\u{1dc0}'..='\u{1dff}'
+            | '\u{20d0}'..='\u{20ff}'
+            | '\u{fe20}'..='\u{fe2f}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{shape, to_framed_png, to_png, FrameOptions, PngOptions, TextFont, THAI_FONT};
+    use crate::error::PromptPayError;
+    use crate::promptpay_utils::{InputType, Utils};
+
+    fn payload() -> String {
+        Utils::generate_payload(InputType::PhoneNumber("0812345678".to_string()), 123.45).unwrap()
+    }
+
+    /// Decode a PNG into its width, height and RGB pixels.
+    fn decode(data: &[u8]) -> (u32, u32, Vec<u8>) {
+        let decoder = png::Decoder::new(data);
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        (info.width, info.height, pixels)
+    }
+
+    #[test]
+    fn test_png_bare_code() {
+        let (width, height, pixels) = decode(&to_png(&payload(), &PngOptions::new()).unwrap());
+        // A version 4 code is 33 modules wide, plus 4 modules of quiet zone on each side
+        assert_eq!((width, height), (328, 328));
+        // The quiet zone is light and the top left finder pattern starts with a dark module
+        assert_eq!(&pixels[..3], &[0xff, 0xff, 0xff]);
+        let corner = ((32 * width + 32) * 3) as usize;
+        assert_eq!(&pixels[corner..corner + 3], &[0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_png_framed_code() {
+        let options = FrameOptions::new(notosans::REGULAR_TTF)
+            .payee_name("SOMTAM SHOP")
+            .amount("123.45 THB")
+            .module_size(4);
+        let (width, height, pixels) = decode(&to_framed_png(&payload(), &options).unwrap());
+        assert_eq!(width, (33 + 4) * 4 + 2 * 12);
+        assert!(height > width);
+        // The header is drawn in the Thai QR Payment blue
+        assert_eq!(&pixels[..3], &[0x0e, 0x3d, 0x67]);
+    }
+
+    #[test]
+    fn test_png_too_large() {
+        for options in [
+            PngOptions::new().module_size(u32::MAX),
+            PngOptions::new().quiet_zone(u32::MAX),
+            PngOptions::new().module_size(1 << 20).quiet_zone(1 << 20),
+        ] {
+            assert_eq!(
+                to_png(&payload(), &options).unwrap_err(),
+                PromptPayError::ImageTooLarge
+            );
+        }
+
+        let options = FrameOptions::new(notosans::REGULAR_TTF).module_size(u32::MAX);
+        assert_eq!(
+            to_framed_png(&payload(), &options).unwrap_err(),
+            PromptPayError::ImageTooLarge
+        );
+    }
+
+    #[test]
+    fn test_png_framed_code_thai_payee() {
+        let font = TextFont::new(THAI_FONT).unwrap();
+        let thai = (0x0e01..=0x0e3a).chain(0x0e3f..=0x0e5b);
+        assert!(thai.filter_map(char::from_u32).all(|c| font.has_glyph(c)));
+
+        let fonts = [font];
+        for glyph in shape(&fonts, "ร้านส้มตำป้าแดง").unwrap() {
+            let bounds = glyph
+                .font
+                .shaper
+                .glyph_bounding_box(glyph.id.into())
+                .unwrap();
+            assert!(bounds.width() > 0 && bounds.height() > 0);
+        }
+
+        let m = 4;
+        let options = FrameOptions::default()
+            .payee_name("ร้านส้มตำป้าแดง")
+            .module_size(m);
+        let (width, _, pixels) = decode(&to_framed_png(&payload(), &options).unwrap());
+        // The payee name is drawn in grey between the code and the bottom border
+        let text_top = (12 + 10 + 33 + 4) * m;
+        let inked = (text_top..text_top + 7 * m)
+            .flat_map(|y| (m..width - m).map(move |x| ((y * width + x) * 3) as usize))
+            .filter(|&offset| pixels[offset..offset + 3] != [0xff, 0xff, 0xff])
+            .count();
+        assert!(inked > 100, "{} pixels of payee name", inked);
+    }
+
+    #[test]
+    fn test_png_thai_marks_stack() {
+        // Ko kai with sara i and mai ek: the tone mark goes above the vowel
+        let fonts = [TextFont::new(THAI_FONT).unwrap()];
+        let glyphs = shape(&fonts, "กิ่").unwrap();
+        assert_eq!(glyphs.len(), 3);
+        let bounds = |index: usize| {
+            let glyph = &glyphs[index];
+            let bounds = glyph
+                .font
+                .shaper
+                .glyph_bounding_box(glyph.id.into())
+                .unwrap();
+            (
+                f32::from(bounds.y_min) + glyph.y_offset,
+                f32::from(bounds.y_max) + glyph.y_offset,
+            )
+        };
+        let (consonant, vowel, tone) = (bounds(0), bounds(1), bounds(2));
+        assert!(vowel.0 >= consonant.1);
+        assert!(tone.0 >= vowel.1);
+    }
+
+    #[test]
+    fn test_png_framed_code_missing_glyph() {
+        // Noto Sans has no Thai glyphs, so a Thai payee name needs a Thai font
+        let options = FrameOptions::new(notosans::REGULAR_TTF).payee_name("ร้านส้มตำ");
+        assert_eq!(
+            to_framed_png(&payload(), &options).unwrap_err(),
+            PromptPayError::MissingGlyph('ร')
+        );
+
+        let options = FrameOptions::new(&b"not a font"[..]);
+        assert_eq!(
+            to_framed_png(&payload(), &options).unwrap_err(),
+            PromptPayError::InvalidFont
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt::Write;
+
+use super::{ErrorCorrection, Matrix};
+use crate::error::PromptPayError;
+
+/// Appearance of an SVG QR code.
+///
+/// ```
+/// use prompt_pay::render::svg::SvgOptions;
+/// use prompt_pay::render::ErrorCorrection;
+///
+/// let options = SvgOptions::new()
+///     .module_size(10)
+///     .dark_color("#1a237e")
+///     .error_correction(ErrorCorrection::Quartile);
+/// assert_eq!(options.quiet_zone, 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvgOptions {
+    /// Size of one module in pixels.
+    pub module_size: u32,
+    /// Width of the blank border around the code, in modules. Scanners expect at least 4.
+    pub quiet_zone: u32,
+    /// Fill colour of the dark modules, in any form SVG accepts.
+    pub dark_color: String,
+    /// Fill colour of the light modules and the quiet zone.
+    pub light_color: String,
+    /// Error-correction level of the code.
+    pub error_correction: ErrorCorrection,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            module_size: 8,
+            quiet_zone: 4,
+            dark_color: "#000000".to_string(),
+            light_color: "#ffffff".to_string(),
+            error_correction: ErrorCorrection::Medium,
+        }
+    }
+}
+
+impl SvgOptions {
+    /// Black on white, 8 pixels per module, with a 4-module quiet zone.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the size of one module in pixels.
+    pub fn module_size(mut self, pixels: u32) -> Self {
+        self.module_size = pixels;
+        self
+    }
+
+    /// Set the width of the quiet zone in modules.
+    pub fn quiet_zone(mut self, modules: u32) -> Self {
+        self.quiet_zone = modules;
+        self
+    }
+
+    /// Set the colour of the dark modules.
+    pub fn dark_color(mut self, color: impl Into<String>) -> Self {
+        self.dark_color = color.into();
+        self
+    }
+
+    /// Set the colour of the light modules and the quiet zone.
+    pub fn light_color(mut self, color: impl Into<String>) -> Self {
+        self.light_color = color.into();
+        self
+    }
+
+    /// Set the error-correction level.
+    pub fn error_correction(mut self, level: ErrorCorrection) -> Self {
+        self.error_correction = level;
+        self
+    }
+}
+
+/// Render a payload string, e.g. the output of `Utils::generate_payload`, as an SVG QR code.
+///
+/// # Parameters
+/// - `payload`: The payload string to encode.
+/// - `options`: Size, colours and error-correction level of the code.
+///
+/// # Returns
+/// A standalone SVG document, or an error if the payload does not fit in a QR code or the
+/// size of the code overflows.
+pub fn to_svg(payload: &str, options: &SvgOptions) -> Result<String, PromptPayError> {
+    let matrix = Matrix::new(payload, options.error_correction)?;
+
+    // Draw in module units and let the viewBox scale them to the requested pixel size
+    let size = matrix.width_with_quiet_zone(options.quiet_zone)?;
+    let quiet_zone = options.quiet_zone as usize;
+    let pixels = (size as u64)
+        .checked_mul(u64::from(options.module_size))
+        .ok_or(PromptPayError::ImageTooLarge)?;
+
+    let mut path = String::new();
+    for y in 0..matrix.width() {
+        for x in 0..matrix.width() {
+            if matrix.is_dark(x, y) {
+                let _ = write!(path, "M{} {}h1v1h-1z", x + quiet_zone, y + quiet_zone);
+            }
+        }
+    }
+
+    Ok(format!(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" version="1.1" width="{pixels}" height="{pixels}" viewBox="0 0 {size} {size}" shape-rendering="crispEdges">"#,
+            r#"<rect width="{size}" height="{size}" fill="{light}"/>"#,
+            r#"<path d="{path}" fill="{dark}"/>"#,
+            "</svg>"
+        ),
+        pixels = pixels,
+        size = size,
+        light = escape_attribute(&options.light_color),
+        dark = escape_attribute(&options.dark_color),
+        path = path,
+    ))
+}
+
+/// Escape a value for use inside a double-quoted XML attribute.
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_svg, SvgOptions};
+    use crate::error::PromptPayError;
+    use crate::promptpay_utils::{InputType, Utils};
+    use crate::render::ErrorCorrection;
+
+    fn payload() -> String {
+        Utils::generate_payload(InputType::PhoneNumber("0812345678".to_string()), 123.45).unwrap()
+    }
+
+    #[test]
+    fn test_svg_default_options() {
+        let svg = to_svg(&payload(), &SvgOptions::new()).unwrap();
+        // A version 4 code is 33 modules wide, plus 4 modules of quiet zone on each side
+        assert!(svg.contains(r#"width="328" height="328" viewBox="0 0 41 41""#));
+        assert!(svg.contains(r##"fill="#ffffff""##));
+        assert!(svg.contains(r##"fill="#000000""##));
+        assert!(svg.ends_with("</svg>"));
+    }
+
+    #[test]
+    fn test_svg_custom_options() {
+        let options = SvgOptions::new()
+            .module_size(2)
+            .quiet_zone(0)
+            .dark_color("navy")
+            .light_color("\"><script>")
+            .error_correction(ErrorCorrection::High);
+        let svg = to_svg(&payload(), &options).unwrap();
+        assert!(svg.contains(r#"fill="navy""#));
+        assert!(svg.contains("fill=\"&quot;&gt;&lt;script&gt;\""));
+        assert!(!svg.contains("<script>"));
+        // High error correction needs a larger version 6 code, 41 modules wide
+        assert!(svg.contains(r#"width="82" height="82" viewBox="0 0 41 41""#));
+    }
+
+    #[test]
+    fn test_svg_payload_too_long() {
+        let result = to_svg(&"0".repeat(8000), &SvgOptions::new());
+        assert_eq!(result.unwrap_err(), PromptPayError::PayloadTooLong);
+    }
+
+    #[test]
+    fn test_svg_too_large() {
+        let options = SvgOptions::new().module_size(u32::MAX).quiet_zone(u32::MAX);
+        let result = to_svg(&payload(), &options);
+        assert_eq!(result.unwrap_err(), PromptPayError::ImageTooLarge);
+    }
+}
@@ -0,0 +1,157 @@
+use super::{ErrorCorrection, Matrix};
+use crate::error::PromptPayError;
+
+/// Black text on a bright white background.
+const ANSI_DARK_ON_LIGHT: &str = "\x1b[30;107m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Appearance of a QR code printed to a terminal.
+///
+/// Each character cell holds two modules stacked vertically, which keeps the code roughly
+/// square in most terminal fonts.
+///
+/// ```
+/// use prompt_pay::render::terminal::TerminalOptions;
+///
+/// // Plain text for a terminal with light text on a dark background
+/// let options = TerminalOptions::new().ansi_color(false).invert(true);
+/// assert_eq!(options.quiet_zone, 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalOptions {
+    /// Width of the blank border around the code, in modules.
+    pub quiet_zone: u32,
+    /// Paint the code black on white with ANSI escape codes, whatever the terminal colours.
+    pub ansi_color: bool,
+    /// Draw the light modules instead of the dark ones. Without ANSI colour, set this on
+    /// terminals with light text on a dark background so the code still scans dark on light.
+    pub invert: bool,
+    /// Error-correction level of the code.
+    pub error_correction: ErrorCorrection,
+}
+
+impl Default for TerminalOptions {
+    fn default() -> Self {
+        TerminalOptions {
+            quiet_zone: 2,
+            ansi_color: true,
+            invert: false,
+            error_correction: ErrorCorrection::Low,
+        }
+    }
+}
+
+impl TerminalOptions {
+    /// ANSI colour, a 2-module quiet zone and low error correction to keep the code small.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the width of the quiet zone in modules.
+    pub fn quiet_zone(mut self, modules: u32) -> Self {
+        self.quiet_zone = modules;
+        self
+    }
+
+    /// Set whether to paint the code with ANSI colour escape codes.
+    pub fn ansi_color(mut self, enabled: bool) -> Self {
+        self.ansi_color = enabled;
+        self
+    }
+
+    /// Set whether to draw the light modules instead of the dark ones.
+    pub fn invert(mut self, enabled: bool) -> Self {
+        self.invert = enabled;
+        self
+    }
+
+    /// Set the error-correction level.
+    pub fn error_correction(mut self, level: ErrorCorrection) -> Self {
+        self.error_correction = level;
+        self
+    }
+}
+
+/// Render a payload string, e.g. the output of `Utils::generate_payload`, as a QR code made
+/// of Unicode half-block characters, ready to print to a terminal.
+///
+/// # Parameters
+/// - `payload`: The payload string to encode.
+/// - `options`: Quiet zone, colour and error-correction level of the code.
+///
+/// # Returns
+/// The lines of the code, each ending in a newline, or an error if the payload does not fit
+/// in a QR code or the size of the code overflows.
+pub fn to_terminal(payload: &str, options: &TerminalOptions) -> Result<String, PromptPayError> {
+    let matrix = Matrix::new(payload, options.error_correction)?;
+    let size = matrix.width_with_quiet_zone(options.quiet_zone)?;
+    let quiet_zone = options.quiet_zone as usize;
+
+    // Modules in the quiet zone, and the padding row under an odd-sized code, are light
+    let is_dark = |x: usize, y: usize| {
+        let inside = |v: usize| v >= quiet_zone && v < quiet_zone + matrix.width();
+        inside(x) && inside(y) && matrix.is_dark(x - quiet_zone, y - quiet_zone)
+    };
+
+    let mut output = String::new();
+    for y in (0..size).step_by(2) {
+        if options.ansi_color {
+            output.push_str(ANSI_DARK_ON_LIGHT);
+        }
+        for x in 0..size {
+            let top = is_dark(x, y) != options.invert;
+            let bottom = is_dark(x, y + 1) != options.invert;
+            output.push(match (top, bottom) {
+                (true, true) => '█',
+                (true, false) => '▀',
+                (false, true) => '▄',
+                (false, false) => ' ',
+            });
+        }
+        if options.ansi_color {
+            output.push_str(ANSI_RESET);
+        }
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_terminal, TerminalOptions};
+    use crate::promptpay_utils::{InputType, Utils};
+
+    fn payload() -> String {
+        Utils::generate_payload(InputType::PhoneNumber("0812345678".to_string()), 123.45).unwrap()
+    }
+
+    #[test]
+    fn test_terminal_plain() {
+        let options = TerminalOptions::new().ansi_color(false);
+        let output = to_terminal(&payload(), &options).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        // A version 3 code at low error correction is 29 modules wide, plus the quiet zone
+        assert_eq!(lines.len(), (29 + 4 + 1) / 2);
+        assert!(lines.iter().all(|line| line.chars().count() == 33));
+        assert_eq!(lines[0].trim(), "");
+        // The second line holds the top two rows of the finder pattern
+        assert!(lines[1].starts_with("  █▀▀▀▀▀█ "));
+    }
+
+    #[test]
+    fn test_terminal_inverted() {
+        let options = TerminalOptions::new().ansi_color(false).invert(true);
+        let output = to_terminal(&payload(), &options).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[0].chars().all(|c| c == '█'));
+        assert!(lines[1].starts_with("██ ▄▄▄▄▄ █"));
+    }
+
+    #[test]
+    fn test_terminal_ansi_color() {
+        let output = to_terminal(&payload(), &TerminalOptions::new()).unwrap();
+        assert!(output
+            .lines()
+            .all(|line| line.starts_with("\x1b[30;107m") && line.ends_with("\x1b[0m")));
+    }
+}
@@ -0,0 +1,87 @@
+use crate::error::{check_digits, Field, PromptPayError};
+
+/// Who a 13-digit Thai identification number was issued to.
+///
+/// Thai citizen IDs and juristic-person tax IDs share the same format and check digit; they
+/// are told apart by the first digit, which is 0 for companies and other juristic persons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaxIdKind {
+    /// A person's national ID, which doubles as their tax ID.
+    Individual,
+    /// The tax ID of a company, partnership or other juristic person.
+    JuristicPerson,
+}
+
+/// Validate a 13-digit Thai national ID or tax ID, including its check digit.
+///
+/// ```
+/// use prompt_pay::tax_id::{self, TaxIdKind};
+///
+/// assert_eq!(tax_id::validate("1234567890121"), Ok(TaxIdKind::Individual));
+/// assert_eq!(tax_id::validate("0107536000315"), Ok(TaxIdKind::JuristicPerson));
+/// assert!(tax_id::validate("1234567890123").is_err());
+/// ```
+///
+/// # Returns
+/// Who the ID was issued to, or an error if it is not 13 digits or the check digit is wrong.
+pub fn validate(id: &str) -> Result<TaxIdKind, PromptPayError> {
+    check(Field::NationalID, id)
+}
+
+/// Validate a tax ID, reporting errors against `field`.
+pub(crate) fn check(field: Field, id: &str) -> Result<TaxIdKind, PromptPayError> {
+    check_digits(field, id, 13)?;
+
+    let digits: Vec<u32> = id.chars().filter_map(|c| c.to_digit(10)).collect();
+    let expected = check_digit(&digits[..12]);
+    if digits[12] != expected {
+        return Err(PromptPayError::InvalidCheckDigit {
+            field,
+            expected: char::from_digit(expected, 10).unwrap_or('0'),
+            actual: id.chars().last().unwrap_or('0'),
+        });
+    }
+
+    Ok(if digits[0] == 0 {
+        TaxIdKind::JuristicPerson
+    } else {
+        TaxIdKind::Individual
+    })
+}
+
+/// The mod-11 check digit of the first 12 digits: weight them 13 down to 2, and subtract
+/// the sum modulo 11 from 11, keeping the last digit.
+fn check_digit(digits: &[u32]) -> u32 {
+    let sum: u32 = digits
+        .iter()
+        .zip((2..=13).rev())
+        .map(|(digit, weight)| digit * weight)
+        .sum();
+    (11 - sum % 11) % 10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate, TaxIdKind};
+    use crate::error::{Field, PromptPayError};
+
+    #[test]
+    fn test_validate_tax_id() {
+        assert_eq!(validate("1234567890121"), Ok(TaxIdKind::Individual));
+        assert_eq!(validate("3100600445635"), Ok(TaxIdKind::Individual));
+        assert_eq!(validate("0107536000315"), Ok(TaxIdKind::JuristicPerson));
+    }
+
+    #[test]
+    fn test_validate_tax_id_check_digit() {
+        assert_eq!(
+            validate("1234567890123"),
+            Err(PromptPayError::InvalidCheckDigit {
+                field: Field::NationalID,
+                expected: '1',
+                actual: '3'
+            })
+        );
+        assert!(validate("123456789012").is_err());
+    }
+}
@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::payload::{PayloadRepr, PromptPayPayload, QrMode};
+use crate::promptpay_utils::{InputType, Utils};
+use crate::render::svg::{self, SvgOptions};
+use crate::render::ErrorCorrection;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = r#"
+export type InputType =
+  | { type: "phone"; value: string }
+  | { type: "national_id"; value: string }
+  | { type: "ewallet_id"; value: string }
+  | { type: "bank_account"; value: { bank_code: string; account_number: string } }
+  | {
+      type: "bill_payment";
+      value: { biller_id: string; reference1: string; reference2?: string | null };
+    };
+
+export interface AdditionalData {
+  bill_number?: string | null;
+  mobile_number?: string | null;
+  store_label?: string | null;
+  loyalty_number?: string | null;
+  reference_label?: string | null;
+  customer_label?: string | null;
+  terminal_label?: string | null;
+  purpose_of_transaction?: string | null;
+}
+
+export interface MerchantInformationLanguage {
+  language_preference: string;
+  merchant_name: string;
+  merchant_city?: string | null;
+}
+
+export interface PromptPayPayload {
+  point_of_initiation?: "static" | "dynamic";
+  proxy: InputType;
+  amount?: string | null;
+  amount_padding?: { type: "none" } | { type: "zero_padded"; value: number };
+  tip_or_convenience_fee?:
+    | { type: "prompt_for_tip" }
+    | { type: "fixed_fee"; value: string }
+    | { type: "percentage_fee"; value: string }
+    | null;
+  currency?: string;
+  country?: string;
+  merchant_category_code?: string | null;
+  merchant_name?: string | null;
+  merchant_city?: string | null;
+  postal_code?: string | null;
+  additional_data?: AdditionalData | null;
+  merchant_information_language?: MerchantInformationLanguage | null;
+}
+
+export interface SvgOptions {
+  moduleSize?: number;
+  quietZone?: number;
+  darkColor?: string;
+  lightColor?: string;
+  errorCorrection?: "low" | "medium" | "quartile" | "high";
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// A JavaScript value typed as `InputType` in the TypeScript definitions.
+    #[wasm_bindgen(typescript_type = "InputType")]
+    pub type JsInputType;
+
+    /// A JavaScript value typed as `PromptPayPayload` in the TypeScript definitions.
+    #[wasm_bindgen(typescript_type = "PromptPayPayload")]
+    pub type JsPromptPayPayload;
+
+    /// A JavaScript value typed as `SvgOptions` in the TypeScript definitions.
+    #[wasm_bindgen(typescript_type = "SvgOptions")]
+    pub type JsSvgOptions;
+}
+
+/// SVG options as passed from JavaScript, with every field optional.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SvgOptionsInput {
+    module_size: Option<u32>,
+    quiet_zone: Option<u32>,
+    dark_color: Option<String>,
+    light_color: Option<String>,
+    error_correction: Option<ErrorCorrection>,
+}
+
+/// Generate a dynamic payload string, as [`Utils::generate_payload`] does.
+///
+/// # Parameters
+/// - `input`: The proxy or bill payment, e.g. `{ type: "phone", value: "0812345678" }`.
+/// - `amount`: The amount in Baht.
+#[wasm_bindgen(js_name = generatePayload)]
+pub fn generate_payload(input: JsInputType, amount: f64) -> Result<String, JsError> {
+    let input: InputType = serde_wasm_bindgen::from_value(input.into())?;
+    Ok(Utils::generate_payload(input, amount)?)
+}
+
+/// Generate a static payload string, where the payer enters the amount.
+#[wasm_bindgen(js_name = generateStaticPayload)]
+pub fn generate_static_payload(input: JsInputType) -> Result<String, JsError> {
+    let input: InputType = serde_wasm_bindgen::from_value(input.into())?;
+    Ok(Utils::generate_payload_with_mode(input, QrMode::Static)?)
+}
+
+/// Encode a full payload model, with merchant fields and additional data, into a payload
+/// string. The proxy is an `InputType` and is sanitized as [`Utils::sanitize_input`] does.
+#[wasm_bindgen(js_name = encodePayload)]
+pub fn encode_payload(payload: JsPromptPayPayload) -> Result<String, JsError> {
+    let repr: PayloadRepr<InputType> = serde_wasm_bindgen::from_value(payload.into())?;
+    Ok(PromptPayPayload::try_from(repr)?.encode()?)
+}
+
+/// Parse a payload string, verifying its CRC, into a payload model.
+#[wasm_bindgen(js_name = parsePayload)]
+pub fn parse_payload(payload: &str) -> Result<JsPromptPayPayload, JsError> {
+    let payload = Utils::parse_payload(payload)?;
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    Ok(payload.serialize(&serializer)?.unchecked_into())
+}
+
+/// Calculate the CRC-16 (XMODEM) of a payload, as [`Utils::calculate_precise_crc`] does.
+#[wasm_bindgen(js_name = calculateCrc)]
+pub fn calculate_crc(payload: &str) -> String {
+    Utils::calculate_precise_crc(payload)
+}
+
+/// Render a payload string as an SVG QR code.
+///
+/// # Parameters
+/// - `payload`: The payload string to encode.
+/// - `options`: Module size, quiet zone, colours and error-correction level; any field left
+///   out keeps its default.
+#[wasm_bindgen(js_name = renderSvg)]
+pub fn render_svg(payload: &str, options: Option<JsSvgOptions>) -> Result<String, JsError> {
+    let mut svg_options = SvgOptions::new();
+    if let Some(options) = options {
+        let options: SvgOptionsInput = serde_wasm_bindgen::from_value(options.into())?;
+        if let Some(module_size) = options.module_size {
+            svg_options.module_size = module_size;
+        }
+        if let Some(quiet_zone) = options.quiet_zone {
+            svg_options.quiet_zone = quiet_zone;
+        }
+        if let Some(dark_color) = options.dark_color {
+            svg_options.dark_color = dark_color;
+        }
+        if let Some(light_color) = options.light_color {
+            svg_options.light_color = light_color;
+        }
+        if let Some(error_correction) = options.error_correction {
+            svg_options.error_correction = error_correction;
+        }
+    }
+    Ok(svg::to_svg(payload, &svg_options)?)
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::{calculate_crc, encode_payload, generate_payload, parse_payload, render_svg};
+    use crate::promptpay_utils::{InputType, Utils};
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn phone() -> InputType {
+        InputType::PhoneNumber("0812345678".to_string())
+    }
+
+    #[wasm_bindgen_test]
+    fn test_wasm_generate_matches_utils() {
+        let input = serde_wasm_bindgen::to_value(&phone()).unwrap();
+        assert_eq!(
+            generate_payload(input.unchecked_into(), 123.45).unwrap(),
+            Utils::generate_payload(phone(), 123.45).unwrap()
+        );
+        assert_eq!(
+            calculate_crc("123456789"),
+            Utils::calculate_precise_crc("123456789")
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_wasm_parse_and_render() {
+        let payload = Utils::generate_payload(phone(), 123.45).unwrap();
+        let parsed = parse_payload(&payload).unwrap();
+        let parsed: serde_json::Value = serde_wasm_bindgen::from_value(parsed.into()).unwrap();
+        assert_eq!(parsed["amount"], "123.45");
+        assert_eq!(parsed["proxy"]["type"], "phone");
+
+        assert!(render_svg(&payload, None).unwrap().starts_with("<?xml"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_wasm_encode_sanitizes_proxy() {
+        let payload = serde_json::json!({
+            "proxy": { "type": "phone", "value": "081-234-5678" },
+            "amount": "123.45",
+        });
+        let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+        let payload = serde::Serialize::serialize(&payload, &serializer).unwrap();
+        let encoded = encode_payload(payload.unchecked_into()).unwrap();
+        assert!(encoded.contains("0066812345678"));
+        assert_eq!(encoded, Utils::generate_payload(phone(), 123.45).unwrap());
+
+        let parsed = parse_payload(&encoded).unwrap();
+        assert_eq!(encode_payload(parsed).unwrap(), encoded);
+
+        let invalid = serde_json::json!({ "proxy": { "type": "phone", "value": "12345" } });
+        let invalid = serde::Serialize::serialize(&invalid, &serializer).unwrap();
+        assert!(encode_payload(invalid.unchecked_into()).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+/*
+ * Exercises the C ABI declared in include/promptpay.h against the static library.
+ * Build and run with `make -C tests/c`.
+ */
+#include <stdio.h>
+#include <string.h>
+
+#include "promptpay.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                             \
+  do {                                                                               \
+    if (!(condition)) {                                                              \
+      fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #condition); \
+      failures++;                                                                    \
+    }                                                                                \
+  } while (0)
+
+static void check_string(const char *actual, const char *expected, int line) {
+  if (actual == NULL || strcmp(actual, expected) != 0) {
+    fprintf(stderr, "%s:%d: expected \"%s\", got \"%s\"\n", __FILE__, line, expected,
+            actual ? actual : "(null)");
+    failures++;
+  }
+}
+
+#define CHECK_STRING(actual, expected) check_string(actual, expected, __LINE__)
+
+static void test_generate(void) {
+  char *payload = NULL;
+
+  CHECK(promptpay_generate(PROMPTPAY_INPUT_PHONE_NUMBER, "081-234-5678", "123.45", &payload) ==
+        PROMPTPAY_STATUS_OK);
+  CHECK_STRING(payload, "00020101021229370016A0000006770101110113006681234567853037645406123.45"
+                        "5802TH6304B5E2");
+  CHECK(promptpay_last_error() == NULL);
+  promptpay_free_string(payload);
+
+  CHECK(promptpay_generate_bank_account("004", "1234567890", NULL, &payload) ==
+        PROMPTPAY_STATUS_OK);
+  CHECK_STRING(payload, "00020101021129370016A0000006770101110413004123456789053037645802TH6304D2C2");
+  promptpay_free_string(payload);
+
+  CHECK(promptpay_generate_bill_payment("123456789012100", "INV001", NULL, "500", &payload) ==
+        PROMPTPAY_STATUS_OK);
+  CHECK_STRING(payload, "00020101021230490016A00000067701011201151234567890121000206INV001"
+                        "53037645406500.005802TH6304E48D");
+  promptpay_free_string(payload);
+}
+
+static void test_generate_errors(void) {
+  char *payload = NULL;
+
+  CHECK(promptpay_generate(PROMPTPAY_INPUT_NATIONAL_ID, "1234567890120", NULL, &payload) ==
+        PROMPTPAY_STATUS_INVALID_INPUT);
+  CHECK(payload == NULL);
+  CHECK(promptpay_last_error() != NULL);
+
+  CHECK(promptpay_generate(PROMPTPAY_INPUT_PHONE_NUMBER, "0812345678", "12.345", &payload) ==
+        PROMPTPAY_STATUS_INVALID_AMOUNT);
+  CHECK(promptpay_generate(42, "0812345678", NULL, &payload) ==
+        PROMPTPAY_STATUS_UNKNOWN_INPUT_TYPE);
+  CHECK(promptpay_generate(PROMPTPAY_INPUT_PHONE_NUMBER, NULL, NULL, &payload) ==
+        PROMPTPAY_STATUS_NULL_POINTER);
+  CHECK_STRING(promptpay_last_error(), "value is null");
+}
+
+static void test_parse(void) {
+  char *json = NULL;
+
+  CHECK(promptpay_parse("00020101021229370016A0000006770101110113006681234567853037645406123.45"
+                        "5802TH6304B5E2",
+                        &json) == PROMPTPAY_STATUS_OK);
+  CHECK(json != NULL && strstr(json, "\"proxy\":{\"type\":\"phone\",\"value\":\"0066812345678\"}"));
+  CHECK(json != NULL && strstr(json, "\"amount\":\"123.45\""));
+  promptpay_free_string(json);
+
+  CHECK(promptpay_parse("00020101021229370016A0000006770101110113006681234567853037645406923.45"
+                        "5802TH6304B5E2",
+                        &json) == PROMPTPAY_STATUS_CRC_MISMATCH);
+  CHECK(promptpay_parse("not a payload", &json) == PROMPTPAY_STATUS_INVALID_PAYLOAD);
+}
+
+static void test_crc(void) {
+  char *crc = NULL;
+
+  CHECK(promptpay_calculate_crc("00020101021129370016A0000006770101110413004123456789053037645802TH"
+                                "6304",
+                                &crc) == PROMPTPAY_STATUS_OK);
+  CHECK_STRING(crc, "D2C2");
+  promptpay_free_string(crc);
+  promptpay_free_string(NULL);
+}
+
+int main(void) {
+  test_generate();
+  test_generate_errors();
+  test_parse();
+  test_crc();
+
+  if (failures > 0) {
+    fprintf(stderr, "%d check(s) failed\n", failures);
+    return 1;
+  }
+  printf("All C ABI checks passed\n");
+  return 0;
+}